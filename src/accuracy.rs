@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+
+use crate::money::Money;
+use crate::strategy::{Action, Situation};
+
+/// The three kinds of decision accuracy is broken down by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Kind {
+    Hard,
+    Soft,
+    Pair,
+}
+
+pub const KINDS: [Kind; 3] = [Kind::Hard, Kind::Soft, Kind::Pair];
+
+impl Kind {
+    pub fn of(situation: Situation) -> Self {
+        match situation {
+            Situation::Hard(_) => Kind::Hard,
+            Situation::Soft(_) => Kind::Soft,
+            Situation::Pair(_) => Kind::Pair,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Kind::Hard => "Hard",
+            Kind::Soft => "Soft",
+            Kind::Pair => "Pairs",
+        }
+    }
+}
+
+/// One kind of wrong play and how often it was made.
+#[derive(Clone, Debug)]
+pub struct Mistake {
+    pub situation: Situation,
+    pub up: usize,
+    pub played: Action,
+    pub best: Action,
+    pub count: usize,
+    /// Expected value given up, in bets.
+    pub cost: f64,
+    /// Expected value given up, in money at the bets that were on the table.
+    pub money_cost: f64,
+}
+
+/// Decisions checked against basic strategy, or the chart in use, over a session.
+#[derive(Default)]
+pub struct Accuracy {
+    decisions: HashMap<Kind, (usize, usize)>,
+    mistakes: HashMap<(Situation, usize, Action), Mistake>,
+}
+
+impl Accuracy {
+    /// Records a play against the right one, given the expected value of every legal action in
+    /// that spot to work out what a mistake costs.
+    pub fn record(
+        &mut self,
+        situation: Situation,
+        up: usize,
+        played: Action,
+        best: Action,
+        actions: &[(Action, f64)],
+        bet: Money,
+    ) {
+        let value_of = |action| {
+            actions
+                .iter()
+                .find(|(a, _)| *a == action)
+                .map_or(0.0, |(_, value)| *value)
+        };
+
+        let (decisions, correct) = self.decisions.entry(Kind::of(situation)).or_default();
+        *decisions += 1;
+
+        if played == best {
+            *correct += 1;
+            return;
+        }
+
+        let cost = value_of(best) - value_of(played);
+
+        let mistake = self
+            .mistakes
+            .entry((situation, up, played))
+            .or_insert(Mistake {
+                situation,
+                up,
+                played,
+                best,
+                count: 0,
+                cost: 0.0,
+                money_cost: 0.0,
+            });
+
+        mistake.count += 1;
+        mistake.cost += cost;
+        mistake.money_cost += cost * bet.as_f64();
+    }
+
+    /// Decisions made and how many were right, for one kind of hand.
+    pub fn by_kind(&self, kind: Kind) -> (usize, usize) {
+        self.decisions.get(&kind).copied().unwrap_or_default()
+    }
+
+    pub fn total(&self) -> (usize, usize) {
+        KINDS.iter().fold((0, 0), |(decisions, correct), kind| {
+            let (d, c) = self.by_kind(*kind);
+            (decisions + d, correct + c)
+        })
+    }
+
+    /// Total expected value given up, in bets and in money.
+    pub fn cost(&self) -> (f64, f64) {
+        self.mistakes.values().fold((0.0, 0.0), |(cost, money), m| {
+            (cost + m.cost, money + m.money_cost)
+        })
+    }
+
+    /// Mistakes, most frequent first and then the most costly.
+    pub fn mistakes(&self) -> Vec<&Mistake> {
+        let mut mistakes: Vec<&Mistake> = self.mistakes.values().collect();
+        mistakes.sort_by(|a, b| b.count.cmp(&a.count).then(b.cost.total_cmp(&a.cost)));
+
+        mistakes
+    }
+}
+
+/// Percentage of `correct` out of `total`, or 0 with no decisions.
+pub fn percentage(correct: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        correct as f64 * 100.0 / total as f64
+    }
+}
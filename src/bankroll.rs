@@ -0,0 +1,158 @@
+use crate::money::Money;
+
+/// The bankroll to work out risk for, and how fast it is played.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bankroll {
+    pub amount: Money,
+    pub hands_per_hour: f64,
+    /// How long a trip lasts, for the risk of ruin within it.
+    pub hours: f64,
+    /// Bankrolls played out hand by hand to check the risk of ruin against.
+    pub paths: usize,
+}
+
+impl Default for Bankroll {
+    fn default() -> Self {
+        Self {
+            amount: Money::whole(10_000),
+            hands_per_hour: 100.0,
+            hours: 100.0,
+            paths: 1000,
+        }
+    }
+}
+
+impl Bankroll {
+    /// Hands in a trip of `hours`.
+    pub fn horizon(&self) -> u64 {
+        (self.hours * self.hands_per_hour).round() as u64
+    }
+}
+
+/// Risk worked out from the average win and standard deviation per hand, treating the
+/// bankroll as a random walk with drift.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Risk {
+    pub hourly_win: f64,
+    pub hourly_deviation: f64,
+    /// Hands until the expected win is one standard deviation, when there is an edge.
+    pub n0: Option<f64>,
+    /// Chance of ever losing the bankroll, playing forever.
+    pub ruin: f64,
+    /// Chance of losing the bankroll within a trip.
+    pub trip_ruin: f64,
+}
+
+impl Risk {
+    pub fn new(mean: f64, deviation: f64, bankroll: &Bankroll) -> Self {
+        let variance = deviation * deviation;
+        let hands = bankroll.horizon() as f64;
+        let amount = bankroll.amount.as_f64();
+
+        let ruin = if mean <= 0.0 {
+            1.0
+        } else {
+            (-2.0 * mean * amount / variance).exp()
+        };
+
+        // First passage below zero within the trip: the chance of ending it under water plus
+        // the chance of having dipped under and come back.
+        let spread = deviation * hands.sqrt();
+        let trip_ruin = if spread == 0.0 {
+            if mean * hands <= -amount {
+                1.0
+            } else {
+                0.0
+            }
+        } else {
+            let below = normal_cdf((-amount - mean * hands) / spread);
+            let returned = (-2.0 * mean * amount / variance
+                + ln_normal_cdf((-amount + mean * hands) / spread))
+            .exp();
+
+            (below + returned).min(1.0)
+        };
+
+        Self {
+            hourly_win: mean * bankroll.hands_per_hour,
+            hourly_deviation: deviation * bankroll.hands_per_hour.sqrt(),
+            n0: (mean > 0.0).then(|| variance / (mean * mean)),
+            ruin,
+            trip_ruin: if trip_ruin.is_nan() { 1.0 } else { trip_ruin },
+        }
+    }
+}
+
+/// Bankrolls played out hand by hand for a trip, and how many went broke.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Paths {
+    pub paths: u64,
+    pub ruined: u64,
+    /// Hands played by the ruined bankrolls before going broke.
+    hands_to_ruin: u64,
+}
+
+impl Paths {
+    pub fn record(&mut self, ruined_after: Option<u64>) {
+        self.paths += 1;
+
+        if let Some(hands) = ruined_after {
+            self.ruined += 1;
+            self.hands_to_ruin += hands;
+        }
+    }
+
+    pub fn add(&mut self, other: &Paths) {
+        self.paths += other.paths;
+        self.ruined += other.ruined;
+        self.hands_to_ruin += other.hands_to_ruin;
+    }
+
+    pub fn ruin(&self) -> f64 {
+        self.ruined as f64 / self.paths.max(1) as f64
+    }
+
+    /// Average hands the ruined bankrolls lasted.
+    pub fn hands_to_ruin(&self) -> Option<f64> {
+        (self.ruined > 0).then(|| self.hands_to_ruin as f64 / self.ruined as f64)
+    }
+}
+
+fn normal_cdf(x: f64) -> f64 {
+    0.5 * erfc(-x / std::f64::consts::SQRT_2)
+}
+
+/// Logarithm of the normal distribution function, accurate far out in the lower tail where
+/// the chance itself would round to zero.
+fn ln_normal_cdf(x: f64) -> f64 {
+    if x < 0.0 {
+        0.5f64.ln() + ln_erfc(-x / std::f64::consts::SQRT_2)
+    } else {
+        normal_cdf(x).ln()
+    }
+}
+
+/// Complementary error function, to a relative error under 1.2e-7 everywhere (Numerical
+/// Recipes' Chebyshev fit).
+fn erfc(x: f64) -> f64 {
+    if x < 0.0 {
+        2.0 - ln_erfc(-x).exp()
+    } else {
+        ln_erfc(x).exp()
+    }
+}
+
+/// Logarithm of `erfc(x)` for `x` of zero or more.
+fn ln_erfc(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.5 * x);
+    let fit = -1.26551223
+        + t * (1.00002368
+            + t * (0.37409196
+                + t * (0.09678418
+                    + t * (-0.18628806
+                        + t * (0.27886807
+                            + t * (-1.13520398
+                                + t * (1.48851587 + t * (-0.82215223 + t * 0.17087277))))))));
+
+    t.ln() - x * x + fit
+}
@@ -0,0 +1,72 @@
+use std::str::FromStr;
+
+use crate::money::Money;
+
+/// A bet this many times over or under the ramp is pointed out.
+const FAR_OFF_FACTOR: i64 = 2;
+
+/// Betting units by true count. Each step applies from its true count up to the next one, and
+/// counts below the first step bet the first step's units.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BetRamp {
+    /// Money per unit.
+    pub unit: Money,
+    /// True counts and units, in increasing order of true count.
+    pub steps: Vec<(f32, usize)>,
+    /// Whether raising or lowering the bet goes straight to the advised bet first.
+    pub jump: bool,
+}
+
+impl Default for BetRamp {
+    fn default() -> Self {
+        Self {
+            unit: Money::whole(50),
+            steps: vec![(0.0, 1), (2.0, 2), (3.0, 4), (4.0, 6), (5.0, 8)],
+            jump: false,
+        }
+    }
+}
+
+impl BetRamp {
+    pub fn units(&self, true_count: f32) -> usize {
+        self.steps
+            .iter()
+            .rev()
+            .find(|(count, _)| true_count >= *count)
+            .or(self.steps.first())
+            .map_or(1, |(_, units)| *units)
+    }
+
+    pub fn bet(&self, true_count: f32) -> Money {
+        self.unit * self.units(true_count) as i64
+    }
+
+    /// Whether a bet is more than double or less than half the advised one.
+    pub fn is_far_off(bet: Money, advised: Money) -> bool {
+        bet > advised * FAR_OFF_FACTOR || bet * FAR_OFF_FACTOR < advised
+    }
+}
+
+/// Parses ramp steps written as `<true count>:<units>`, comma separated, e.g. `0:1, 2:2, 3:4`.
+pub fn parse_steps(text: &str) -> Result<Vec<(f32, usize)>, String> {
+    let mut steps = Vec::new();
+
+    for step in text.split(',') {
+        let invalid = || format!("expected a step like `2:4`, got `{}`", step.trim());
+
+        let (count, units) = step.split_once(':').ok_or_else(invalid)?;
+        let count = f32::from_str(count.trim()).map_err(|_| invalid())?;
+        let units = match units.trim().parse::<usize>() {
+            Ok(units) if units > 0 => units,
+            _ => return Err(invalid()),
+        };
+
+        steps.push((count, units));
+    }
+
+    if steps.windows(2).any(|pair| pair[0].0 >= pair[1].0) {
+        return Err("ramp steps must go up in true count".to_string());
+    }
+
+    Ok(steps)
+}
@@ -1,3 +1,5 @@
+use std::{fmt, str::FromStr};
+
 pub const DECK_SIZE: usize = 52;
 
 #[derive(Clone, Debug)]
@@ -8,7 +10,7 @@ pub enum Suit {
     Spade,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Rank {
     Ace,
     Two,
@@ -23,6 +25,129 @@ pub enum Rank {
     Jack,
     Queen,
     King,
+    Joker,
+}
+
+/// Every rank, including the joker that only custom compositions put in the shoe.
+pub const RANKS: [Rank; 14] = [
+    Rank::Ace,
+    Rank::Two,
+    Rank::Three,
+    Rank::Four,
+    Rank::Five,
+    Rank::Six,
+    Rank::Seven,
+    Rank::Eight,
+    Rank::Nine,
+    Rank::Ten,
+    Rank::Jack,
+    Rank::Queen,
+    Rank::King,
+    Rank::Joker,
+];
+
+const STANDARD_RANKS: &[Rank] = RANKS.split_at(13).0;
+
+pub type RankCounts = [usize; RANKS.len()];
+
+impl Rank {
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Rank::Ace => "A",
+            Rank::Two => "2",
+            Rank::Three => "3",
+            Rank::Four => "4",
+            Rank::Five => "5",
+            Rank::Six => "6",
+            Rank::Seven => "7",
+            Rank::Eight => "8",
+            Rank::Nine => "9",
+            Rank::Ten => "10",
+            Rank::Jack => "J",
+            Rank::Queen => "Q",
+            Rank::King => "K",
+            Rank::Joker => "JK",
+        }
+    }
+}
+
+impl FromStr for Rank {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+
+        RANKS
+            .iter()
+            .find(|rank| {
+                rank.symbol().eq_ignore_ascii_case(text) || (text == "T" && **rank == Rank::Ten)
+            })
+            .copied()
+            .ok_or_else(|| format!("unknown rank `{}`", text))
+    }
+}
+
+/// What a joker is worth in a hand.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JokerRule {
+    /// Whatever brings the hand closest to 21 without going over, at least 1.
+    Wild,
+    Fixed(i32),
+}
+
+impl FromStr for JokerRule {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.trim() {
+            "wild" => Ok(JokerRule::Wild),
+            value => match value.parse::<i32>() {
+                Ok(value) if (1..=11).contains(&value) => Ok(JokerRule::Fixed(value)),
+                _ => Err(format!("joker must be `wild` or 1-11, got `{}`", value)),
+            },
+        }
+    }
+}
+
+impl fmt::Display for JokerRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JokerRule::Wild => write!(f, "wild"),
+            JokerRule::Fixed(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// The cards that make up a shoe: a number of decks with ranks removed, extra copies of ranks
+/// added and jokers mixed in per deck.
+#[derive(Clone, Debug)]
+pub struct Composition {
+    pub number_of_decks: usize,
+    pub removed: Vec<Rank>,
+    pub extra: Vec<(Rank, usize)>,
+    pub jokers: usize,
+    pub joker_rule: JokerRule,
+}
+
+impl Composition {
+    pub fn standard(number_of_decks: usize) -> Self {
+        Self {
+            number_of_decks,
+            removed: Vec::new(),
+            extra: Vec::new(),
+            jokers: 0,
+            joker_rule: JokerRule::Wild,
+        }
+    }
+
+    /// Cards in each deck once ranks are taken out and extra cards and jokers added.
+    pub fn deck_size(&self) -> usize {
+        Card::generate_custom_deck(self).len() / self.number_of_decks.max(1)
+    }
 }
 
 #[derive(Debug)]
@@ -49,10 +174,22 @@ impl Card {
         self.hidden = false;
     }
 
+    pub fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+
+    pub fn rank(&self) -> Rank {
+        self.rank
+    }
+
     pub fn is_ace(&self) -> bool {
         self.rank == Rank::Ace
     }
 
+    pub fn is_joker(&self) -> bool {
+        self.rank == Rank::Joker
+    }
+
     pub fn value(&self) -> i32 {
         match self.rank {
             Rank::Ace => 11,
@@ -65,14 +202,45 @@ impl Card {
             Rank::Eight => 8,
             Rank::Nine => 9,
             Rank::Ten | Rank::Jack | Rank::Queen | Rank::King => 10,
+            Rank::Joker => 0,
         }
     }
 
+    /// Short ASCII name such as `10H` or `AS`, used in the fairness log.
+    pub fn code(&self) -> String {
+        let suit_letter = match self.suit {
+            Suit::Club => "C",
+            Suit::Diamond => "D",
+            Suit::Heart => "H",
+            Suit::Spade => "S",
+        };
+
+        format!("{}{}", self.rank.symbol(), suit_letter)
+    }
+
+    /// Reads a card back from its `code`, like `10H` or `JKS`.
+    pub fn from_code(code: &str) -> Option<Card> {
+        let suit = match code.get(code.len().checked_sub(1)?..)? {
+            "C" => Suit::Club,
+            "D" => Suit::Diamond,
+            "H" => Suit::Heart,
+            "S" => Suit::Spade,
+            _ => return None,
+        };
+        let rank = code[..code.len() - 1].parse().ok()?;
+
+        Some(Card::new(suit, rank))
+    }
+
     pub fn as_compact_string(&self) -> String {
         if self.hidden {
             return "?".to_string();
         }
 
+        if self.is_joker() {
+            return "Joker".to_string();
+        }
+
         let suit_text = match self.suit {
             Suit::Club => ("♣", "Clubs"),
             Suit::Diamond => ("♦", "Diamonds"),
@@ -94,6 +262,7 @@ impl Card {
             Rank::Jack => format!("Jack of {}", suit_text.0),
             Rank::Queen => format!("Queen of {}", suit_text.0),
             Rank::King => format!("King of {}", suit_text.0),
+            Rank::Joker => "Joker".to_string(),
         }
     }
 
@@ -210,6 +379,13 @@ impl Card {
                 text.push(format!("│     {} │", suit_icon));
                 text.push("│      K│".to_string());
             }
+            Rank::Joker => {
+                text.push("│J      │".to_string());
+                text.push("│ JOKER │".to_string());
+                text.push(format!("│   {}   │", suit_icon));
+                text.push("│ JOKER │".to_string());
+                text.push("│      J│".to_string());
+            }
         }
 
         text.push("└───────┘".to_string());
@@ -219,32 +395,45 @@ impl Card {
 
     pub fn generate_deck(number_of_decks: usize) -> Vec<Card> {
         let suits = [Suit::Diamond, Suit::Heart, Suit::Club, Suit::Spade];
-        let ranks = [
-            Rank::Ace,
-            Rank::Two,
-            Rank::Three,
-            Rank::Four,
-            Rank::Five,
-            Rank::Six,
-            Rank::Seven,
-            Rank::Eight,
-            Rank::Nine,
-            Rank::Ten,
-            Rank::Jack,
-            Rank::Queen,
-            Rank::King,
-        ];
-
-        let mut deck = Vec::new();
+
+        let mut deck = Vec::with_capacity(DECK_SIZE * number_of_decks);
 
         for suit in &suits {
-            for rank in &ranks {
+            for rank in STANDARD_RANKS {
                 for _ in 0..number_of_decks {
-                    deck.push(Card::new(suit.clone(), rank.clone()));
+                    deck.push(Card::new(suit.clone(), *rank));
                 }
             }
         }
 
         deck
     }
+
+    /// Builds a shoe from a custom composition. Extra copies and jokers are spread across the
+    /// suits in turn.
+    pub fn generate_custom_deck(composition: &Composition) -> Vec<Card> {
+        let suits = [Suit::Diamond, Suit::Heart, Suit::Club, Suit::Spade];
+
+        let mut deck = Card::generate_deck(composition.number_of_decks);
+        deck.retain(|card| !composition.removed.contains(&card.rank));
+
+        for _ in 0..composition.number_of_decks {
+            for (rank, copies) in &composition.extra {
+                for copy in 0..*copies {
+                    deck.push(Card::new(suits[copy % suits.len()].clone(), *rank));
+                }
+            }
+
+            for joker in 0..composition.jokers {
+                let suit = if joker % 2 == 0 {
+                    Suit::Heart
+                } else {
+                    Suit::Spade
+                };
+                deck.push(Card::new(suit, Rank::Joker));
+            }
+        }
+
+        deck
+    }
 }
@@ -0,0 +1,464 @@
+use std::{collections::HashMap, fmt, fs, path::Path, str::FromStr};
+
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::card::{Card, JokerRule, Rank};
+use crate::game::Game;
+use crate::rules::Rules;
+use crate::strategy::{
+    best_action, card_of_value, card_value, legal_actions, value_symbol, Action, BasicStrategy,
+    Hint, Situation, Strategy,
+};
+
+/// Rows a chart must have, with every up-card filled in.
+const HARD_TOTALS: std::ops::RangeInclusive<usize> = 5..=20;
+const SOFT_TOTALS: std::ops::RangeInclusive<usize> = 13..=20;
+
+/// A cell of a strategy chart, in the usual shorthand.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Code {
+    /// `H`
+    Hit,
+    /// `S`
+    Stand,
+    /// `D` or `Dh`: double, otherwise hit.
+    DoubleOrHit,
+    /// `Ds`: double, otherwise stand.
+    DoubleOrStand,
+    /// `P`: split, otherwise play the hand by its total.
+    Split,
+    /// `Ph`: split, otherwise hit.
+    SplitOrHit,
+    /// `Rh`: surrender, otherwise hit.
+    SurrenderOrHit,
+    /// `Rs`: surrender, otherwise stand.
+    SurrenderOrStand,
+    /// `Rp`: surrender, otherwise split.
+    SurrenderOrSplit,
+}
+
+impl Code {
+    /// Actions in order of preference, the first the rules allow being played.
+    pub fn preferences(&self) -> &'static [Action] {
+        use Action::*;
+
+        match self {
+            Code::Hit => &[Hit],
+            Code::Stand => &[Stand],
+            Code::DoubleOrHit => &[Double, Hit],
+            Code::DoubleOrStand => &[Double, Stand],
+            Code::Split => &[Split],
+            Code::SplitOrHit => &[Split, Hit],
+            Code::SurrenderOrHit => &[Surrender, Hit],
+            Code::SurrenderOrStand => &[Surrender, Stand],
+            Code::SurrenderOrSplit => &[Surrender, Split],
+        }
+    }
+
+    /// The action the code starts with.
+    pub fn action(&self) -> Action {
+        self.preferences()[0]
+    }
+}
+
+impl fmt::Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let code = match self {
+            Code::Hit => "H",
+            Code::Stand => "S",
+            Code::DoubleOrHit => "D",
+            Code::DoubleOrStand => "Ds",
+            Code::Split => "P",
+            Code::SplitOrHit => "Ph",
+            Code::SurrenderOrHit => "Rh",
+            Code::SurrenderOrStand => "Rs",
+            Code::SurrenderOrSplit => "Rp",
+        };
+
+        write!(f, "{}", code)
+    }
+}
+
+impl FromStr for Code {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "h" => Ok(Code::Hit),
+            "s" => Ok(Code::Stand),
+            "d" | "dh" => Ok(Code::DoubleOrHit),
+            "ds" => Ok(Code::DoubleOrStand),
+            "p" => Ok(Code::Split),
+            "ph" => Ok(Code::SplitOrHit),
+            "rh" => Ok(Code::SurrenderOrHit),
+            "rs" => Ok(Code::SurrenderOrStand),
+            "rp" => Ok(Code::SurrenderOrSplit),
+            _ => Err(format!(
+                "unknown action code `{}`, expected H, S, D, Dh, Ds, P, Ph, Rh, Rs or Rp",
+                text.trim()
+            )),
+        }
+    }
+}
+
+/// The three tables of a chart.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Table {
+    Hard,
+    Soft,
+    Pairs,
+}
+
+pub const TABLES: [Table; 3] = [Table::Hard, Table::Soft, Table::Pairs];
+
+impl Table {
+    pub fn of(situation: Situation) -> Self {
+        match situation {
+            Situation::Hard(_) => Table::Hard,
+            Situation::Soft(_) => Table::Soft,
+            Situation::Pair(_) => Table::Pairs,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Table::Hard => "Hard",
+            Table::Soft => "Soft",
+            Table::Pairs => "Pairs",
+        }
+    }
+
+    /// The hands a complete table has a row for, top to bottom.
+    pub fn rows(&self) -> Vec<Situation> {
+        match self {
+            Table::Hard => HARD_TOTALS.map(Situation::Hard).collect(),
+            Table::Soft => SOFT_TOTALS.map(Situation::Soft).collect(),
+            Table::Pairs => (1..=10).map(Situation::Pair).collect(),
+        }
+    }
+
+    /// How a row is labelled on the chart: `16`, `A7` or `8-8`.
+    pub fn label(situation: Situation) -> String {
+        match situation {
+            Situation::Hard(total) => total.to_string(),
+            Situation::Soft(total) => format!("A{}", total - 11),
+            Situation::Pair(value) => {
+                let card = value_symbol(value);
+                format!("{}-{}", card, card)
+            }
+        }
+    }
+}
+
+/// A strategy chart of hard totals, soft totals and pairs by dealer up-card, read from CSV.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chart {
+    codes: HashMap<(Situation, usize), Code>,
+}
+
+impl Chart {
+    /// Reads a chart from a CSV file, checking every cell is filled in.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|error| format!("cannot read chart {}: {}", path.display(), error))?;
+
+        Self::parse(&text).map_err(|message| format!("{}: {}", path.display(), message))
+    }
+
+    /// Parses a chart of three tables, each starting with a header row naming the table and the
+    /// up-cards, e.g. `hard,2,3,4,5,6,7,8,9,10,A`, followed by a row for each hand: hard totals
+    /// 5-20, soft totals 13-20 (or `A2` to `A9`) and pairs `A` to `10` (or `8-8`). `#` starts a
+    /// comment.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut codes = HashMap::new();
+        let mut table: Option<(Table, Vec<usize>)> = None;
+
+        for (number, line) in text.lines().enumerate() {
+            let line = match line.split_once('#') {
+                Some((before, _)) => before.trim(),
+                None => line.trim(),
+            };
+
+            if line.trim_matches(',').trim().is_empty() {
+                continue;
+            }
+
+            let cells: Vec<&str> = line.split(',').map(str::trim).collect();
+            let in_line = |message: String| format!("line {}: {}", number + 1, message);
+
+            if let Some(kind) = table_of(cells[0]) {
+                table = Some((kind, parse_up_cards(&cells[1..]).map_err(in_line)?));
+                continue;
+            }
+
+            let Some((kind, ups)) = &table else {
+                return Err(in_line(
+                    "expected a header row like `hard,2,3,4,5,6,7,8,9,10,A` first".to_string(),
+                ));
+            };
+
+            let situation = parse_hand(*kind, cells[0]).map_err(in_line)?;
+
+            if cells.len() - 1 != ups.len() {
+                return Err(in_line(format!(
+                    "expected {} plays for {}, got {}",
+                    ups.len(),
+                    situation,
+                    cells.len() - 1
+                )));
+            }
+
+            for (up, cell) in ups.iter().zip(&cells[1..]) {
+                let code = cell.parse().map_err(in_line)?;
+
+                if codes.insert((situation, *up), code).is_some() {
+                    return Err(in_line(format!(
+                        "{} v {} is given twice",
+                        situation,
+                        value_symbol(*up)
+                    )));
+                }
+            }
+        }
+
+        let chart = Self { codes };
+        let missing: Vec<String> = Self::cells()
+            .filter(|(situation, up)| chart.code(*situation, *up).is_none())
+            .map(|(situation, up)| format!("{} v {}", situation, value_symbol(up)))
+            .collect();
+
+        match missing.as_slice() {
+            [] => Ok(chart),
+            [only] => Err(format!("the chart has no play for {}", only)),
+            [first, rest @ ..] => Err(format!(
+                "the chart has no play for {} and {} more",
+                first,
+                rest.len()
+            )),
+        }
+    }
+
+    /// Every hand and up-card a complete chart has, up-cards as 1 (ace) to 10.
+    pub fn cells() -> impl Iterator<Item = (Situation, usize)> {
+        TABLES
+            .iter()
+            .flat_map(Table::rows)
+            .flat_map(|situation| (1..=10).map(move |up| (situation, up)))
+    }
+
+    /// The engine's plays written out as a chart, from a two card hand for each cell. Splits
+    /// only go in the pairs table.
+    pub fn from_strategy(strategy: &BasicStrategy) -> Self {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut codes = HashMap::new();
+
+        for (situation, up) in Self::cells() {
+            let hand = situation.example_hand(&mut rng);
+            let up_card = card_of_value(up, &mut rng);
+            let mut actions = strategy.evaluate(&hand, &up_card);
+
+            if Table::of(situation) != Table::Pairs {
+                actions.retain(|(action, _)| *action != Action::Split);
+            }
+
+            let value_of = |played| {
+                actions
+                    .iter()
+                    .find(|(action, _)| *action == played)
+                    .map_or(f64::MIN, |(_, value)| *value)
+            };
+            let otherwise_hit = value_of(Action::Hit) >= value_of(Action::Stand);
+
+            let code = match (best_action(&actions), otherwise_hit) {
+                (Action::Hit, _) => Code::Hit,
+                (Action::Stand, _) => Code::Stand,
+                (Action::Double, true) => Code::DoubleOrHit,
+                (Action::Double, false) => Code::DoubleOrStand,
+                (Action::Split, _) => Code::Split,
+                (Action::Surrender, true) => Code::SurrenderOrHit,
+                (Action::Surrender, false) => Code::SurrenderOrStand,
+            };
+
+            codes.insert((situation, up), code);
+        }
+
+        Self { codes }
+    }
+
+    pub fn code(&self, situation: Situation, up: usize) -> Option<Code> {
+        self.codes.get(&(situation, up)).copied()
+    }
+
+    /// The chart's play for a hand out of the legal actions. Totals off the chart are played as
+    /// the nearest row, and a pair that cannot be split as its total.
+    pub fn play(
+        &self,
+        hand: &[Card],
+        up_card: &Card,
+        legal: &[Action],
+        joker_rule: JokerRule,
+    ) -> Action {
+        let up = card_value(up_card.rank()).unwrap_or(1);
+        let preferred = |situation| {
+            self.code(row_of(situation), up).and_then(|code| {
+                code.preferences()
+                    .iter()
+                    .copied()
+                    .find(|action| legal.contains(action))
+            })
+        };
+
+        let situation = Situation::of(hand, joker_rule);
+
+        preferred(situation)
+            .or_else(|| {
+                let total = Game::hand_value(hand, joker_rule) as usize;
+
+                if Game::is_soft(hand, joker_rule) {
+                    preferred(Situation::Soft(total))
+                } else {
+                    preferred(Situation::Hard(total))
+                }
+            })
+            .unwrap_or(Action::Hit)
+    }
+
+    /// The chart's play for a hand, with the chart cell it comes from.
+    pub fn hint(
+        &self,
+        hand: &[Card],
+        up_card: &Card,
+        legal: &[Action],
+        joker_rule: JokerRule,
+    ) -> Hint {
+        let situation = Situation::of(hand, joker_rule);
+        let up = card_value(up_card.rank()).unwrap_or(1);
+        let code = self
+            .code(row_of(situation), up)
+            .map_or("-".to_string(), |code| code.to_string());
+
+        Hint {
+            action: self.play(hand, up_card, legal, joker_rule),
+            reason: format!(
+                "{} v {}: {} on the chart",
+                situation,
+                value_symbol(up),
+                code
+            ),
+        }
+    }
+}
+
+/// A chart played under the table rules, for the simulator.
+pub struct ChartStrategy {
+    chart: Chart,
+    rules: Rules,
+    joker_rule: JokerRule,
+}
+
+impl ChartStrategy {
+    pub fn new(chart: Chart, rules: &Rules, joker_rule: JokerRule) -> Self {
+        Self {
+            chart,
+            rules: rules.clone(),
+            joker_rule,
+        }
+    }
+}
+
+impl Strategy for ChartStrategy {
+    fn play(&self, hand: &[Card], up_card: &Card, _true_count: f32) -> Action {
+        let legal = legal_actions(hand, &self.rules);
+        self.chart.play(hand, up_card, &legal, self.joker_rule)
+    }
+}
+
+/// The row a hand is looked up in, totals off the chart taking the nearest one.
+pub fn row_of(situation: Situation) -> Situation {
+    match situation {
+        Situation::Hard(total) => {
+            Situation::Hard(total.clamp(*HARD_TOTALS.start(), *HARD_TOTALS.end()))
+        }
+        Situation::Soft(total) => {
+            Situation::Soft(total.clamp(*SOFT_TOTALS.start(), *SOFT_TOTALS.end()))
+        }
+        pair => pair,
+    }
+}
+
+fn table_of(cell: &str) -> Option<Table> {
+    match cell.to_ascii_lowercase().as_str() {
+        "hard" => Some(Table::Hard),
+        "soft" => Some(Table::Soft),
+        "pair" | "pairs" => Some(Table::Pairs),
+        _ => None,
+    }
+}
+
+fn parse_up_cards(cells: &[&str]) -> Result<Vec<usize>, String> {
+    let mut ups = Vec::new();
+
+    for cell in cells {
+        let up = match cell.parse::<Rank>().map(card_value) {
+            Ok(Some(up)) => up,
+            _ => return Err(format!("unknown up-card `{}`", cell)),
+        };
+
+        if ups.contains(&up) {
+            return Err(format!(
+                "up-card {} is in the header twice",
+                value_symbol(up)
+            ));
+        }
+
+        ups.push(up);
+    }
+
+    Ok(ups)
+}
+
+/// A row label: a total, `A7` or `A-7` for soft hands, and `8`, `88` or `8-8` for pairs.
+fn parse_hand(table: Table, label: &str) -> Result<Situation, String> {
+    let invalid = || format!("unknown hand `{}`", label);
+    let value_of = |card: &str| card.parse::<Rank>().ok().and_then(card_value);
+
+    match table {
+        Table::Hard => match label.parse() {
+            Ok(total) if HARD_TOTALS.contains(&total) => Ok(Situation::Hard(total)),
+            _ => Err(invalid()),
+        },
+        Table::Soft => {
+            let total = match label.parse() {
+                Ok(total) => total,
+                Err(_) => {
+                    let upper = label.to_ascii_uppercase();
+                    let other = upper.strip_prefix('A').ok_or_else(invalid)?;
+                    11 + value_of(other.trim_start_matches('-')).ok_or_else(invalid)?
+                }
+            };
+
+            if SOFT_TOTALS.contains(&total) {
+                Ok(Situation::Soft(total))
+            } else {
+                Err(invalid())
+            }
+        }
+        Table::Pairs => {
+            if let Some(value) = value_of(label) {
+                return Ok(Situation::Pair(value));
+            }
+
+            // Halving a label like `é9` by bytes would land inside a character.
+            let (first, second) = match label.split_once('-') {
+                Some(halves) => halves,
+                None if label.is_ascii() => label.split_at(label.len() / 2),
+                None => return Err(invalid()),
+            };
+
+            match (value_of(first), value_of(second)) {
+                (Some(first), Some(second)) if first == second => Ok(Situation::Pair(first)),
+                _ => Err(invalid()),
+            }
+        }
+    }
+}
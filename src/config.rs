@@ -0,0 +1,361 @@
+use std::{
+    fs,
+    io::{Error, ErrorKind},
+    path::Path,
+    time::Duration,
+};
+
+use crate::bankroll::Bankroll;
+use crate::betting::{self, BetRamp};
+use crate::card::{Card, Composition, Rank};
+use crate::chart::Chart;
+use crate::count::TagCount;
+use crate::deviation::{self, Deviation};
+use crate::game::MIN_BET;
+use crate::money::Money;
+use crate::rules::Rules;
+use crate::shuffle::ShuffleModel;
+
+pub const CONFIG_PATH: &str = "blackjack.conf";
+
+/// Enough for a few rounds between shuffles.
+const MIN_SHOE_SIZE: usize = 20;
+
+/// Settings read from an INI style file of `[section]` headers and `key = value` lines, with
+/// `#` starting a comment. Anything left out keeps its default.
+#[derive(Clone)]
+pub struct Config {
+    pub composition: Composition,
+    pub shuffle: ShuffleModel,
+    pub rules: Rules,
+    pub always_hint: bool,
+    /// Strategy chart hints and accuracy go by instead of the strategy engine.
+    pub chart: Option<Chart>,
+    pub counting_system: TagCount,
+    /// Built in systems and any defined in `[count.<name>]` sections, to pick the one named in
+    /// `[count] system` from once the whole file is read.
+    counting_systems: Vec<TagCount>,
+    counting_system_name: Option<String>,
+    /// How long each card shows for in the countdown drill.
+    pub countdown_interval: Duration,
+    /// Cards dealt at once in the countdown drill, 1 or 2.
+    pub countdown_cards: usize,
+    /// Index plays for the deviation trainer, built in sets first and then any of your own.
+    pub deviations: Vec<Deviation>,
+    custom_deviations: Vec<Deviation>,
+    pub ramp: BetRamp,
+    pub bankroll: Bankroll,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            composition: Composition::standard(2),
+            shuffle: ShuffleModel::default(),
+            rules: Rules::default(),
+            always_hint: false,
+            chart: None,
+            counting_system: TagCount::hi_lo(),
+            counting_systems: TagCount::built_in(),
+            counting_system_name: None,
+            countdown_interval: Duration::from_millis(1000),
+            countdown_cards: 1,
+            deviations: [deviation::illustrious_18(), deviation::fab_4()].concat(),
+            custom_deviations: Vec::new(),
+            ramp: BetRamp::default(),
+            bankroll: Bankroll::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Reads the config at `path`, falling back to the defaults if the file does not exist.
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).map_err(|message| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("{}: {}", path.display(), message),
+                )
+            }),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut config = Self::default();
+        let mut section = String::new();
+
+        for (number, line) in text.lines().enumerate() {
+            let line = match line.split_once('#') {
+                Some((before, _)) => before.trim(),
+                None => line.trim(),
+            };
+
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = name.trim().to_string();
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(format!("line {}: expected `key = value`", number + 1));
+            };
+
+            config
+                .set(&section, key.trim(), value.trim())
+                .map_err(|message| format!("line {}: {}", number + 1, message))?;
+        }
+
+        if let Some(name) = &config.counting_system_name {
+            config.counting_system = config
+                .counting_systems
+                .iter()
+                .rev()
+                .find(|system| same_name(&system.name, name))
+                .cloned()
+                .ok_or_else(|| format!("unknown counting system `{}`", name))?;
+        }
+
+        let custom_deviations = std::mem::take(&mut config.custom_deviations);
+        config.deviations.extend(custom_deviations);
+
+        let shoe_size = Card::generate_custom_deck(&config.composition).len();
+
+        if shoe_size < MIN_SHOE_SIZE {
+            return Err(format!(
+                "the shoe has {} cards, it needs at least {}",
+                shoe_size, MIN_SHOE_SIZE
+            ));
+        }
+
+        Ok(config)
+    }
+
+    /// The `[shoe]` settings as one `key=value;...` word, so the fair log can record how each
+    /// shoe was built.
+    pub fn shoe_settings(&self) -> String {
+        let composition = &self.composition;
+        let removed: Vec<&str> = composition.removed.iter().map(Rank::symbol).collect();
+        let extra: Vec<String> = composition
+            .extra
+            .iter()
+            .map(|(rank, copies)| format!("{}*{}", rank.symbol(), copies))
+            .collect();
+
+        format!(
+            "decks={};remove={};extra={};jokers={};joker={};shuffle={}",
+            composition.number_of_decks,
+            removed.join(","),
+            extra.join(","),
+            composition.jokers,
+            composition.joker_rule,
+            self.shuffle
+        )
+    }
+
+    /// The defaults with `[shoe]` settings written by `shoe_settings` applied.
+    pub fn with_shoe_settings(settings: &str) -> Result<Self, String> {
+        let mut config = Self::default();
+
+        for setting in settings.split(';') {
+            let Some((key, value)) = setting.split_once('=') else {
+                return Err(format!("expected `key=value`, got `{}`", setting));
+            };
+
+            config.set("shoe", key, value)?;
+        }
+
+        Ok(config)
+    }
+
+    fn set(&mut self, section: &str, key: &str, value: &str) -> Result<(), String> {
+        match (section, key) {
+            ("shoe", "decks") => self.composition.number_of_decks = parse_count(value, 1)?,
+            ("shoe", "remove") => self.composition.removed = parse_ranks(value)?,
+            ("shoe", "extra") => self.composition.extra = parse_extra(value)?,
+            ("shoe", "jokers") => self.composition.jokers = parse_count(value, 0)?,
+            ("shoe", "joker") => self.composition.joker_rule = value.parse()?,
+            ("shoe", "shuffle") => self.shuffle = value.parse()?,
+            ("rules", "dealer_hits_soft_17") => self.rules.dealer_hits_soft_17 = parse_bool(value)?,
+            ("rules", "charlie") => {
+                self.rules.charlie = match parse_count(value, 0)? {
+                    0 => None,
+                    cards if cards >= 3 => Some(cards),
+                    _ => return Err("charlie must be 0 (off) or at least 3 cards".to_string()),
+                }
+            }
+            ("rules", "win_pays") => self.rules.win_pays = parse_positive(value)?,
+            ("rules", "bust_push") => self.rules.bust_push = parse_bool(value)?,
+            ("rules", "double") => self.rules.double = parse_bool(value)?,
+            ("rules", "split") => self.rules.split = parse_bool(value)?,
+            ("rules", "surrender") => self.rules.surrender = parse_bool(value)?,
+            ("hints", "always") => self.always_hint = parse_bool(value)?,
+            ("strategy", "chart") => self.chart = Some(Chart::load(Path::new(value))?),
+            ("countdown", "speed") => {
+                self.countdown_interval = Duration::try_from_secs_f32(parse_positive(value)?)
+                    .map_err(|_| format!("`{}` seconds is too long", value))?
+            }
+            ("countdown", "cards") => {
+                self.countdown_cards = match parse_count(value, 1)? {
+                    cards @ (1 | 2) => cards,
+                    _ => return Err("cards must be 1 or 2".to_string()),
+                }
+            }
+            ("betting", "unit") => self.ramp.unit = parse_money(value)?,
+            ("betting", "ramp") => self.ramp.steps = betting::parse_steps(value)?,
+            ("betting", "jump") => self.ramp.jump = parse_bool(value)?,
+            ("bankroll", "amount") => {
+                let amount = parse_money(value)?;
+
+                if amount < MIN_BET {
+                    return Err(format!(
+                        "expected a bankroll of at least the {} table minimum, got `{}`",
+                        MIN_BET, value
+                    ));
+                }
+
+                self.bankroll.amount = amount;
+            }
+            ("bankroll", "hands_per_hour") => {
+                self.bankroll.hands_per_hour = parse_positive(value)? as f64
+            }
+            ("bankroll", "hours") => self.bankroll.hours = parse_positive(value)? as f64,
+            ("bankroll", "paths") => self.bankroll.paths = parse_count(value, 0)?,
+            ("deviations", "sets") => {
+                self.deviations = Vec::new();
+
+                for name in value.split(',').map(str::trim) {
+                    match deviation::built_in(name) {
+                        Some(set) => self.deviations.extend(set),
+                        None if name == "none" => (),
+                        None => return Err(format!("unknown deviation set `{}`", name)),
+                    }
+                }
+            }
+            ("deviations", spot) => self.custom_deviations.push(Deviation::parse(spot, value)?),
+            ("count", "system") => self.counting_system_name = Some(value.to_string()),
+            (custom, "tags" | "key_count") if custom.starts_with("count.") => {
+                self.set_custom_count(&custom["count.".len()..], key, value)?
+            }
+            _ => return Err(format!("unknown setting `{}` in [{}]", key, section)),
+        }
+
+        Ok(())
+    }
+
+    fn set_custom_count(&mut self, name: &str, key: &str, value: &str) -> Result<(), String> {
+        let index = match self
+            .counting_systems
+            .iter()
+            .position(|system| system.name == name)
+        {
+            Some(index) => index,
+            None => {
+                self.counting_systems.push(TagCount {
+                    name: name.to_string(),
+                    tags: [0.0; 10],
+                    key_counts: Vec::new(),
+                });
+                self.counting_systems.len() - 1
+            }
+        };
+
+        let system = &mut self.counting_systems[index];
+
+        match key {
+            "tags" => {
+                let tags: Vec<f32> = value
+                    .split(',')
+                    .map(|tag| tag.trim().parse::<f32>())
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| format!("tags must be numbers, got `{}`", value))?;
+
+                system.tags = tags
+                    .try_into()
+                    .map_err(|_| "expected 10 tags, for A, 2-9 and 10".to_string())?;
+            }
+            _ => {
+                let key_count = value
+                    .parse::<f32>()
+                    .map_err(|_| format!("expected a number, got `{}`", value))?;
+
+                system.key_counts = vec![(1, key_count)];
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_count(value: &str, min: usize) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(count) if count >= min => Ok(count),
+        _ => Err(format!(
+            "expected a whole number of at least {}, got `{}`",
+            min, value
+        )),
+    }
+}
+
+fn parse_positive(value: &str) -> Result<f32, String> {
+    match value.parse::<f32>() {
+        Ok(number) if number > 0.0 && number.is_finite() => Ok(number),
+        _ => Err(format!("expected a positive number, got `{}`", value)),
+    }
+}
+
+fn parse_money(value: &str) -> Result<Money, String> {
+    match value.parse::<Money>() {
+        Ok(amount) if amount > Money::ZERO => Ok(amount),
+        _ => Err(format!(
+            "expected a positive amount of money, got `{}`",
+            value
+        )),
+    }
+}
+
+/// Compares counting system names ignoring case, spaces and dashes, so `hi-lo` finds `Hi-Lo`.
+fn same_name(a: &str, b: &str) -> bool {
+    let simplify = |name: &str| -> String {
+        name.chars()
+            .filter(char::is_ascii_alphanumeric)
+            .map(|c| c.to_ascii_lowercase())
+            .collect()
+    };
+
+    simplify(a) == simplify(b)
+}
+
+fn parse_bool(value: &str) -> Result<bool, String> {
+    match value {
+        "true" | "yes" | "on" => Ok(true),
+        "false" | "no" | "off" => Ok(false),
+        _ => Err(format!("expected true or false, got `{}`", value)),
+    }
+}
+
+/// Comma separated ranks, e.g. `5, 10`.
+fn parse_ranks(value: &str) -> Result<Vec<Rank>, String> {
+    value
+        .split(',')
+        .filter(|item| !item.trim().is_empty())
+        .map(str::parse)
+        .collect()
+}
+
+/// Comma separated `rank*copies` pairs, e.g. `10*4, A*1`.
+fn parse_extra(value: &str) -> Result<Vec<(Rank, usize)>, String> {
+    value
+        .split(',')
+        .filter(|item| !item.trim().is_empty())
+        .map(|item| match item.split_once('*') {
+            Some((rank, copies)) => Ok((rank.parse()?, parse_count(copies.trim(), 1)?)),
+            None => Err(format!("expected `rank*copies`, got `{}`", item.trim())),
+        })
+        .collect()
+}
@@ -0,0 +1,208 @@
+use crate::card::{Card, Rank, RANKS};
+use crate::strategy::card_value;
+
+/// A card counting system: a tag for every rank, plus how the running count turns into
+/// something to bet and play by.
+pub trait CountingSystem {
+    fn name(&self) -> &str;
+
+    fn tag(&self, rank: Rank) -> f32;
+
+    /// Sum of the tags over one standard deck, 0 for a balanced system.
+    fn imbalance(&self) -> f32 {
+        let per_suit: f32 = RANKS
+            .iter()
+            .filter(|rank| **rank != Rank::Joker)
+            .map(|rank| self.tag(*rank))
+            .sum();
+
+        per_suit * 4.0
+    }
+
+    fn is_balanced(&self) -> bool {
+        self.imbalance().abs() < f32::EPSILON
+    }
+
+    /// Running count at the start of a shoe. Unbalanced systems start low enough that a full
+    /// shoe counts up to the imbalance of one deck, their pivot.
+    fn initial_running_count(&self, number_of_decks: usize) -> f32 {
+        -self.imbalance() * (number_of_decks as f32 - 1.0)
+    }
+
+    /// Running count at which an unbalanced system starts raising bets.
+    fn key_count(&self, _number_of_decks: usize) -> Option<f32> {
+        None
+    }
+
+    /// Running count per deck left, for decks of `deck_size` cards. Unbalanced counts first take
+    /// off the drift expected from the cards dealt so far, which gives the balanced equivalent.
+    fn true_count(
+        &self,
+        running_count: f32,
+        number_of_decks: usize,
+        deck_size: usize,
+        cards_dealt: usize,
+        cards_remaining: usize,
+    ) -> f32 {
+        let drift = self.initial_running_count(number_of_decks)
+            + self.imbalance() * cards_dealt as f32 / deck_size as f32;
+
+        (running_count - drift) / decks_remaining(cards_remaining, deck_size)
+    }
+}
+
+/// A counting system given as a table of tags by card value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TagCount {
+    pub name: String,
+    /// Tags from ace to the ten-valued ranks.
+    pub tags: [f32; 10],
+    /// Key counts by number of decks, in increasing order. Shoes in between are interpolated and
+    /// a single entry applies to every shoe.
+    pub key_counts: Vec<(usize, f32)>,
+}
+
+impl TagCount {
+    fn new(name: &str, tags: [f32; 10]) -> Self {
+        Self {
+            name: name.to_string(),
+            tags,
+            key_counts: Vec::new(),
+        }
+    }
+
+    pub fn hi_lo() -> Self {
+        Self::new(
+            "Hi-Lo",
+            [-1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, -1.0],
+        )
+    }
+
+    /// Knock-Out, unbalanced by +4 a deck, with the key counts from the book.
+    pub fn ko() -> Self {
+        Self {
+            key_counts: vec![(1, 2.0), (2, 1.0), (6, -4.0), (8, -6.0)],
+            ..Self::new("KO", [-1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0, -1.0])
+        }
+    }
+
+    pub fn omega_ii() -> Self {
+        Self::new(
+            "Omega II",
+            [0.0, 1.0, 1.0, 2.0, 2.0, 2.0, 1.0, 0.0, -1.0, -2.0],
+        )
+    }
+
+    pub fn zen() -> Self {
+        Self::new("Zen", [-1.0, 1.0, 1.0, 2.0, 2.0, 2.0, 1.0, 0.0, 0.0, -2.0])
+    }
+
+    pub fn wong_halves() -> Self {
+        Self::new(
+            "Wong Halves",
+            [-1.0, 0.5, 1.0, 1.0, 1.5, 1.0, 0.5, 0.0, -0.5, -1.0],
+        )
+    }
+
+    pub fn built_in() -> Vec<Self> {
+        vec![
+            Self::hi_lo(),
+            Self::ko(),
+            Self::omega_ii(),
+            Self::zen(),
+            Self::wong_halves(),
+        ]
+    }
+}
+
+impl CountingSystem for TagCount {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn tag(&self, rank: Rank) -> f32 {
+        card_value(rank).map_or(0.0, |value| self.tags[value - 1])
+    }
+
+    fn key_count(&self, number_of_decks: usize) -> Option<f32> {
+        let decks = number_of_decks as f32;
+
+        let below = self
+            .key_counts
+            .iter()
+            .rev()
+            .find(|(d, _)| *d <= number_of_decks);
+        let above = self.key_counts.iter().find(|(d, _)| *d >= number_of_decks);
+
+        match (below, above) {
+            (Some((d1, k1)), Some((d2, k2))) if d1 != d2 => {
+                let (d1, d2) = (*d1 as f32, *d2 as f32);
+                Some((k1 + (k2 - k1) * (decks - d1) / (d2 - d1)).round())
+            }
+            (Some((_, key)), _) | (None, Some((_, key))) => Some(*key),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Running count of the cards seen since the shoe was last shuffled.
+pub struct Counter {
+    system: Box<dyn CountingSystem + Send>,
+    number_of_decks: usize,
+    /// Cards in each deck, which custom compositions and jokers change.
+    deck_size: usize,
+    running: f32,
+}
+
+impl Counter {
+    pub fn new(
+        system: Box<dyn CountingSystem + Send>,
+        number_of_decks: usize,
+        deck_size: usize,
+    ) -> Self {
+        let running = system.initial_running_count(number_of_decks);
+
+        Self {
+            system,
+            number_of_decks,
+            deck_size,
+            running,
+        }
+    }
+
+    pub fn see(&mut self, card: &Card) {
+        self.running += self.system.tag(card.rank());
+    }
+
+    pub fn reset(&mut self) {
+        self.running = self.system.initial_running_count(self.number_of_decks);
+    }
+
+    pub fn system(&self) -> &dyn CountingSystem {
+        self.system.as_ref()
+    }
+
+    pub fn running(&self) -> f32 {
+        self.running
+    }
+
+    pub fn key_count(&self) -> Option<f32> {
+        self.system.key_count(self.number_of_decks)
+    }
+
+    pub fn true_count(&self, cards_dealt: usize, cards_remaining: usize) -> f32 {
+        self.system.true_count(
+            self.running,
+            self.number_of_decks,
+            self.deck_size,
+            cards_dealt,
+            cards_remaining,
+        )
+    }
+}
+
+/// Decks left to deal, never less than half a deck so the true count stays sensible at the end
+/// of the shoe.
+pub fn decks_remaining(cards_remaining: usize, deck_size: usize) -> f32 {
+    (cards_remaining as f32 / deck_size as f32).max(0.5)
+}
@@ -0,0 +1,185 @@
+use std::time::{Duration, Instant};
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use crate::card::{Card, DECK_SIZE};
+use crate::config::Config;
+use crate::count::Counter;
+
+/// Most cards kept back from the end of the deck, so the final count is not always the same.
+const MAX_HELD_BACK: usize = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Phase {
+    Dealing,
+    Answering,
+    Done,
+}
+
+/// The outcome of counting down one deck.
+#[derive(Clone, Copy, Debug)]
+pub struct Run {
+    pub running_count: f32,
+    pub answer: f32,
+    pub cards: usize,
+    /// From the first card to the answer.
+    pub time: Duration,
+}
+
+impl Run {
+    pub fn is_correct(&self) -> bool {
+        (self.running_count - self.answer).abs() < 0.01
+    }
+}
+
+/// Counting practice: a shuffled deck is dealt a card or two at a time at a set speed, apart
+/// from a few held back cards, and the final running count is asked for.
+pub struct Countdown {
+    rng: StdRng,
+    counter: Counter,
+    cards_at_a_time: usize,
+    interval: Duration,
+    deck: Vec<Card>,
+    showing: Vec<Card>,
+    held_back: Vec<Card>,
+    dealt: usize,
+    phase: Phase,
+    started: Instant,
+    last_deal: Instant,
+    runs: Vec<Run>,
+}
+
+impl Countdown {
+    pub fn new(config: &Config) -> Self {
+        let now = Instant::now();
+
+        let mut countdown = Self {
+            rng: StdRng::from_entropy(),
+            counter: Counter::new(Box::new(config.counting_system.clone()), 1, DECK_SIZE),
+            cards_at_a_time: config.countdown_cards,
+            interval: config.countdown_interval,
+            deck: Vec::new(),
+            showing: Vec::new(),
+            held_back: Vec::new(),
+            dealt: 0,
+            phase: Phase::Done,
+            started: now,
+            last_deal: now,
+            runs: Vec::new(),
+        };
+
+        countdown.start();
+
+        countdown
+    }
+
+    /// Shuffles a fresh deck and deals the first cards.
+    pub fn start(&mut self) {
+        self.deck = Card::generate_deck(1);
+        self.deck.shuffle(&mut self.rng);
+
+        let held_back = self.rng.gen_range(1..=MAX_HELD_BACK);
+        self.held_back = self.deck.split_off(self.deck.len() - held_back);
+
+        self.counter.reset();
+        self.dealt = 0;
+        self.phase = Phase::Dealing;
+        self.started = Instant::now();
+        self.deal();
+    }
+
+    /// Moves on to the next cards once they have been showing for the set time.
+    pub fn update(&mut self) {
+        if self.phase == Phase::Dealing && self.last_deal.elapsed() >= self.interval {
+            self.deal();
+        }
+    }
+
+    /// Deals the next cards straight away, or asks for the count once the deck is done.
+    pub fn deal(&mut self) {
+        if self.phase != Phase::Dealing {
+            return;
+        }
+
+        if self.deck.is_empty() {
+            self.showing.clear();
+            self.phase = Phase::Answering;
+            return;
+        }
+
+        self.showing.clear();
+
+        for _ in 0..self.cards_at_a_time {
+            if let Some(card) = self.deck.pop() {
+                self.counter.see(&card);
+                self.showing.push(card);
+                self.dealt += 1;
+            }
+        }
+
+        self.last_deal = Instant::now();
+    }
+
+    /// Time until the next cards are due, for waiting on input in between.
+    pub fn time_to_next_deal(&self) -> Option<Duration> {
+        match self.phase {
+            Phase::Dealing => Some(self.interval.saturating_sub(self.last_deal.elapsed())),
+            _ => None,
+        }
+    }
+
+    pub fn answer(&mut self, answer: f32) {
+        if self.phase != Phase::Answering {
+            return;
+        }
+
+        self.runs.push(Run {
+            running_count: self.counter.running(),
+            answer,
+            cards: self.dealt,
+            time: self.started.elapsed(),
+        });
+
+        self.phase = Phase::Done;
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    pub fn showing(&self) -> &[Card] {
+        &self.showing
+    }
+
+    /// Cards never dealt, only meant to be shown once the count is in.
+    pub fn held_back(&self) -> &[Card] {
+        &self.held_back
+    }
+
+    pub fn cards_left(&self) -> usize {
+        self.deck.len()
+    }
+
+    pub fn system_name(&self) -> &str {
+        self.counter.system().name()
+    }
+
+    pub fn runs(&self) -> &[Run] {
+        &self.runs
+    }
+
+    /// Runs counted right, out of all of them.
+    pub fn accuracy(&self) -> (usize, usize) {
+        let correct = self.runs.iter().filter(|run| run.is_correct()).count();
+        (correct, self.runs.len())
+    }
+
+    /// Fastest correct run.
+    pub fn best_time(&self) -> Option<Duration> {
+        self.runs
+            .iter()
+            .filter(|run| run.is_correct())
+            .map(|run| run.time)
+            .min()
+    }
+}
@@ -0,0 +1,435 @@
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::card::{Card, Rank};
+use crate::strategy::{
+    best_action, card_of_value, card_value, value_symbol, Action, BasicStrategy, Situation,
+    Strategy,
+};
+
+/// How far either side of its index the true count of a drilled spot is picked from.
+const COUNT_SPREAD: i32 = 3;
+
+/// Plays the trainer has keys for.
+const DRILLED_ACTIONS: [Action; 5] = [
+    Action::Hit,
+    Action::Stand,
+    Action::Double,
+    Action::Split,
+    Action::Surrender,
+];
+
+/// When an index play is made.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Trigger {
+    /// At this true count or higher.
+    AtLeast(f32),
+    /// Below this true count.
+    Below(f32),
+}
+
+impl Trigger {
+    pub fn index(&self) -> f32 {
+        match self {
+            Trigger::AtLeast(index) | Trigger::Below(index) => *index,
+        }
+    }
+
+    pub fn applies(&self, true_count: f32) -> bool {
+        match self {
+            Trigger::AtLeast(index) => true_count >= *index,
+            Trigger::Below(index) => true_count < *index,
+        }
+    }
+}
+
+impl FromStr for Trigger {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+
+        if let Some(index) = text.strip_prefix(">=") {
+            index.trim().parse().map(Trigger::AtLeast).map_err(|_| ())
+        } else if let Some(index) = text.strip_prefix('<') {
+            index.trim().parse().map(Trigger::Below).map_err(|_| ())
+        } else {
+            Err(())
+        }
+        .map_err(|_| format!("expected a trigger like `>= 0` or `< -1`, got `{}`", text))
+    }
+}
+
+/// A change from basic strategy once the true count passes an index.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Deviation {
+    pub situation: Situation,
+    pub up: usize,
+    pub action: Action,
+    pub trigger: Trigger,
+    /// The play when the trigger does not apply.
+    pub otherwise: Action,
+}
+
+impl Deviation {
+    fn new(
+        situation: Situation,
+        up: usize,
+        action: Action,
+        trigger: Trigger,
+        otherwise: Action,
+    ) -> Self {
+        Self {
+            situation,
+            up,
+            action,
+            trigger,
+            otherwise,
+        }
+    }
+
+    /// Parses a config entry, the spot as `hard 16 v 10` and the play as `stand >= 0 else hit`
+    /// or `hit < -1 else stand`.
+    pub fn parse(spot: &str, play: &str) -> Result<Self, String> {
+        let (situation, up) = spot
+            .split_once(" v ")
+            .ok_or_else(|| format!("expected a spot like `hard 16 v 10`, got `{}`", spot))?;
+
+        let situation = situation.parse()?;
+        let up = match up.trim().parse::<Rank>().map(card_value) {
+            Ok(Some(up)) => up,
+            _ => return Err(format!("unknown up-card `{}`", up.trim())),
+        };
+
+        let invalid = || format!("expected a play like `stand >= 0 else hit`, got `{}`", play);
+
+        let (condition, otherwise) = play.split_once(" else ").ok_or_else(invalid)?;
+        let (action, trigger) = condition.split_at(condition.find(['>', '<']).ok_or_else(invalid)?);
+
+        Ok(Self::new(
+            situation,
+            up,
+            action.parse()?,
+            trigger.parse()?,
+            otherwise.parse()?,
+        ))
+    }
+
+    pub fn play(&self, true_count: f32) -> Action {
+        if self.trigger.applies(true_count) {
+            self.action
+        } else {
+            self.otherwise
+        }
+    }
+
+    fn spot(&self) -> (Situation, usize) {
+        (self.situation, self.up)
+    }
+}
+
+impl fmt::Display for Deviation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (comparison, index) = match self.trigger {
+            Trigger::AtLeast(index) => ("≥", index),
+            Trigger::Below(index) => ("<", index),
+        };
+
+        write!(
+            f,
+            "{} v {}: {} at TC {} {:+}, otherwise {}",
+            self.situation,
+            value_symbol(self.up),
+            self.action,
+            comparison,
+            index,
+            self.otherwise
+        )
+    }
+}
+
+/// The Illustrious 18 for Hi-Lo, less insurance which this table does not offer.
+pub fn illustrious_18() -> Vec<Deviation> {
+    use Action::*;
+    use Situation::*;
+    use Trigger::*;
+
+    vec![
+        Deviation::new(Hard(16), 10, Stand, AtLeast(0.0), Hit),
+        Deviation::new(Hard(15), 10, Stand, AtLeast(4.0), Hit),
+        Deviation::new(Pair(10), 5, Split, AtLeast(5.0), Stand),
+        Deviation::new(Pair(10), 6, Split, AtLeast(4.0), Stand),
+        Deviation::new(Hard(10), 10, Double, AtLeast(4.0), Hit),
+        Deviation::new(Hard(12), 3, Stand, AtLeast(2.0), Hit),
+        Deviation::new(Hard(12), 2, Stand, AtLeast(3.0), Hit),
+        Deviation::new(Hard(11), 1, Double, AtLeast(1.0), Hit),
+        Deviation::new(Hard(9), 2, Double, AtLeast(1.0), Hit),
+        Deviation::new(Hard(10), 1, Double, AtLeast(4.0), Hit),
+        Deviation::new(Hard(9), 7, Double, AtLeast(3.0), Hit),
+        Deviation::new(Hard(16), 9, Stand, AtLeast(5.0), Hit),
+        Deviation::new(Hard(13), 2, Hit, Below(-1.0), Stand),
+        Deviation::new(Hard(12), 4, Hit, Below(0.0), Stand),
+        Deviation::new(Hard(12), 5, Hit, Below(-2.0), Stand),
+        Deviation::new(Hard(12), 6, Hit, Below(-1.0), Stand),
+        Deviation::new(Hard(13), 3, Hit, Below(-2.0), Stand),
+    ]
+}
+
+/// The Fab 4 late surrender plays for Hi-Lo.
+pub fn fab_4() -> Vec<Deviation> {
+    use Action::*;
+    use Situation::*;
+    use Trigger::*;
+
+    vec![
+        Deviation::new(Hard(14), 10, Surrender, AtLeast(3.0), Hit),
+        Deviation::new(Hard(15), 10, Surrender, AtLeast(0.0), Hit),
+        Deviation::new(Hard(15), 9, Surrender, AtLeast(2.0), Hit),
+        Deviation::new(Hard(15), 1, Surrender, AtLeast(1.0), Hit),
+    ]
+}
+
+/// A named set of built in deviations, as given in the config.
+pub fn built_in(name: &str) -> Option<Vec<Deviation>> {
+    match name.to_ascii_lowercase().as_str() {
+        "illustrious-18" | "i18" => Some(illustrious_18()),
+        "fab-4" | "fab4" => Some(fab_4()),
+        _ => None,
+    }
+}
+
+/// The right play for a spot at a true count. Deviations later in the table win over earlier
+/// ones for the same spot once they apply, so surrender plays can be layered over the rest.
+/// Deviations to a play that isn't in `legal` are dropped first, so a surrender the table
+/// doesn't offer can't hide the index play under it.
+pub fn play(
+    deviations: &[Deviation],
+    situation: Situation,
+    up: usize,
+    true_count: f32,
+    legal: &[Action],
+) -> Option<Action> {
+    let spot: Vec<&Deviation> = deviations
+        .iter()
+        .filter(|deviation| {
+            deviation.spot() == (situation, up) && legal.contains(&deviation.action)
+        })
+        .collect();
+
+    spot.iter()
+        .rev()
+        .find(|deviation| deviation.trigger.applies(true_count))
+        .or(spot.first())
+        .map(|deviation| deviation.play(true_count))
+}
+
+/// Basic strategy with index plays on top, made whenever the rules allow them.
+pub struct IndexStrategy {
+    basic: BasicStrategy,
+    deviations: Vec<Deviation>,
+}
+
+impl IndexStrategy {
+    pub fn new(basic: BasicStrategy, deviations: Vec<Deviation>) -> Self {
+        Self { basic, deviations }
+    }
+}
+
+impl Strategy for IndexStrategy {
+    fn play(&self, hand: &[Card], up_card: &Card, true_count: f32) -> Action {
+        let actions = self.basic.evaluate(hand, up_card);
+        let legal: Vec<Action> = actions.iter().map(|(action, _)| *action).collect();
+        let situation = Situation::of(hand, self.basic.joker_rule());
+        let up = card_value(up_card.rank()).unwrap_or(1);
+
+        match play(&self.deviations, situation, up, true_count, &legal) {
+            Some(action) if legal.contains(&action) => action,
+            _ => best_action(&actions),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Phase {
+    Choosing,
+    Answered { played: Action, correct: bool },
+}
+
+/// A deviation played wrong, and how often.
+#[derive(Clone, Debug)]
+pub struct Miss {
+    pub deviation: Deviation,
+    pub count: usize,
+}
+
+/// Drills index plays: a hand, the dealer's up-card and a true count near one of the indexes,
+/// scored against the deviation table.
+pub struct DeviationTrainer {
+    rng: StdRng,
+    deviations: Vec<Deviation>,
+    /// Index of the deviation being drilled.
+    current: usize,
+    hand: Vec<Card>,
+    up_card: Vec<Card>,
+    true_count: f32,
+    phase: Phase,
+    answered: usize,
+    correct: usize,
+    misses: HashMap<usize, Miss>,
+}
+
+impl DeviationTrainer {
+    /// Needs at least one deviation to drill.
+    pub fn new(deviations: Vec<Deviation>) -> Self {
+        let mut trainer = Self {
+            rng: StdRng::from_entropy(),
+            deviations,
+            current: 0,
+            hand: Vec::new(),
+            up_card: Vec::new(),
+            true_count: 0.0,
+            phase: Phase::Choosing,
+            answered: 0,
+            correct: 0,
+            misses: HashMap::new(),
+        };
+
+        trainer.next();
+
+        trainer
+    }
+
+    /// Deals the next spot to play.
+    pub fn next(&mut self) {
+        self.current = self.rng.gen_range(0..self.deviations.len());
+        let deviation = self.deviation();
+
+        let index = deviation.trigger.index().round() as i32;
+        self.true_count =
+            self.rng
+                .gen_range(index - COUNT_SPREAD..=index + COUNT_SPREAD) as f32;
+
+        self.hand = deviation.situation.example_hand(&mut self.rng);
+        self.up_card = vec![card_of_value(deviation.up, &mut self.rng)];
+        self.phase = Phase::Choosing;
+    }
+
+    pub fn choose(&mut self, played: Action) {
+        if self.phase != Phase::Choosing {
+            return;
+        }
+
+        let correct = played == self.right_play();
+
+        self.answered += 1;
+
+        if correct {
+            self.correct += 1;
+        } else {
+            let deviation = self.deviation();
+
+            self.misses
+                .entry(self.current)
+                .or_insert(Miss {
+                    deviation,
+                    count: 0,
+                })
+                .count += 1;
+        }
+
+        self.phase = Phase::Answered { played, correct };
+    }
+
+    /// The deviation the spot showing was dealt for.
+    pub fn deviation(&self) -> Deviation {
+        self.deviations[self.current]
+    }
+
+    /// The play the table gives for the spot showing. Every play has a key in the drill, so
+    /// none of the deviations are left out.
+    pub fn right_play(&self) -> Action {
+        let deviation = self.deviation();
+
+        play(
+            &self.deviations,
+            deviation.situation,
+            deviation.up,
+            self.true_count,
+            &DRILLED_ACTIONS,
+        )
+        .unwrap_or(deviation.otherwise)
+    }
+
+    /// Every deviation for the spot showing, to explain the answer.
+    pub fn spot_deviations(&self) -> Vec<&Deviation> {
+        let spot = self.deviation().spot();
+
+        self.deviations
+            .iter()
+            .filter(|deviation| deviation.spot() == spot)
+            .collect()
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    pub fn hand(&self) -> &[Card] {
+        &self.hand
+    }
+
+    pub fn up_card(&self) -> &[Card] {
+        &self.up_card
+    }
+
+    pub fn true_count(&self) -> f32 {
+        self.true_count
+    }
+
+    /// Spots answered and how many were right.
+    pub fn score(&self) -> (usize, usize) {
+        (self.answered, self.correct)
+    }
+
+    /// Deviations played wrong, most often first.
+    pub fn misses(&self) -> Vec<&Miss> {
+        let mut misses: Vec<&Miss> = self.misses.values().collect();
+        misses.sort_by_key(|miss| std::cmp::Reverse(miss.count));
+
+        misses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_deviations_the_rules_do_not_allow() {
+        let deviations = [illustrious_18(), fab_4()].concat();
+        let hit_or_stand = [Action::Hit, Action::Stand];
+
+        // The Fab 4 surrender on 15 v 10 would otherwise hide the Illustrious 18 stand.
+        for (true_count, expected) in [(0.0, Action::Hit), (3.0, Action::Hit), (4.0, Action::Stand)]
+        {
+            assert_eq!(
+                play(
+                    &deviations,
+                    Situation::Hard(15),
+                    10,
+                    true_count,
+                    &hit_or_stand
+                ),
+                Some(expected)
+            );
+        }
+
+        let with_surrender = [Action::Hit, Action::Stand, Action::Surrender];
+
+        assert_eq!(
+            play(&deviations, Situation::Hard(15), 10, 4.0, &with_surrender),
+            Some(Action::Surrender)
+        );
+    }
+}
@@ -0,0 +1,374 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs::{self, File, OpenOptions},
+    io::Write,
+    path::Path,
+};
+
+use sha2::{Digest, Sha256};
+
+use crate::card::Card;
+use crate::config::Config;
+use crate::shoe::{Shoe, ShoeRecord};
+
+pub const FAIR_LOG_PATH: &str = "blackjack-fair.log";
+
+pub type Seed = [u8; 32];
+
+/// SHA-256 over the seed, the order the cards were gathered up in and the shuffled shoe, so
+/// none of them can be swapped after the commitment is published.
+pub fn commitment(seed: &Seed, gathered: &[String], cards: &[Card]) -> String {
+    let order: Vec<String> = cards.iter().rev().map(Card::code).collect();
+
+    let mut hasher = Sha256::new();
+    hasher.update(to_hex(seed));
+    hasher.update(":");
+    hasher.update(gathered.join(","));
+    hasher.update(":");
+    hasher.update(order.join(","));
+
+    to_hex(&hasher.finalize())
+}
+
+/// Appends a `commit <hash> <shoe settings>` line before a shoe is played and a
+/// `reveal <hash> <seed> <gathered cards> <dealt cards...>` line once it is exhausted, the
+/// gathered cards comma separated.
+pub struct FairLog {
+    file: File,
+    /// The `[shoe]` settings every shoe is built with, so the log can be checked without them.
+    settings: String,
+    committed: usize,
+    revealed: usize,
+}
+
+impl FairLog {
+    pub fn open(path: &Path, config: &Config) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+
+        Ok(Self {
+            file,
+            settings: config.shoe_settings(),
+            committed: 0,
+            revealed: 0,
+        })
+    }
+
+    /// Publishes commitments for new shoes and reveals the ones that have been exhausted.
+    pub fn sync(&mut self, shoe: &Shoe) -> std::io::Result<()> {
+        self.write(shoe.records(), shoe.records().len() - 1)
+    }
+
+    /// Reveals every shoe, including the one in play, at the end of the session.
+    pub fn finish(&mut self, shoe: &Shoe) -> std::io::Result<()> {
+        self.write(shoe.records(), shoe.records().len())
+    }
+
+    fn write(&mut self, records: &[ShoeRecord], reveal_up_to: usize) -> std::io::Result<()> {
+        for (index, record) in records.iter().enumerate() {
+            if index >= self.committed {
+                writeln!(self.file, "commit {} {}", record.commitment, self.settings)?;
+                self.committed += 1;
+            }
+
+            if index >= self.revealed && index < reveal_up_to {
+                writeln!(
+                    self.file,
+                    "reveal {} {} {} {}",
+                    record.commitment,
+                    to_hex(&record.seed),
+                    record.gathered.join(","),
+                    record.dealt.join(" ")
+                )?;
+                self.revealed += 1;
+            }
+        }
+
+        self.file.flush()
+    }
+}
+
+/// How one shoe in the log checked out.
+#[derive(Debug, PartialEq)]
+pub enum Check {
+    /// The shoe matched its commitment and dealt this many cards as it should have.
+    Ok { shoe: String, dealt: usize },
+    /// A shoe, or a line of the log, that did not check out.
+    Fail { shoe: String, message: String },
+    /// A shoe committed to but not revealed yet.
+    Open { shoe: String },
+}
+
+/// Rebuilds every revealed shoe in the log from its seed and the shoe settings it was
+/// committed with, and checks it against its commitment and the cards that were dealt from it.
+pub fn verify(path: &Path) -> std::io::Result<Vec<Check>> {
+    let log = fs::read_to_string(path)?;
+
+    let mut committed: Vec<(String, Result<Config, String>)> = Vec::new();
+    let mut revealed = HashSet::new();
+    let mut checks = Vec::new();
+
+    for (number, line) in log.lines().enumerate() {
+        let mut words = line.split_whitespace();
+
+        match (words.next(), words.next()) {
+            (Some("commit"), Some(hash)) => {
+                let config = match words.next() {
+                    Some(settings) => Config::with_shoe_settings(settings),
+                    None => Err("no shoe settings with the commitment".to_string()),
+                };
+
+                committed.push((hash.to_string(), config));
+            }
+            (Some("reveal"), Some(hash)) => {
+                let seed = words.next().and_then(from_hex);
+                let gathered = words.next().unwrap_or_default();
+                let config = committed
+                    .iter()
+                    .find(|(committed, _)| committed == hash)
+                    .map(|(_, config)| config);
+
+                let result = match (seed, config) {
+                    (None, _) => Err("invalid seed".to_string()),
+                    (_, None) => Err("revealed without an earlier commitment".to_string()),
+                    (_, Some(Err(message))) => Err(message.clone()),
+                    (Some(seed), Some(Ok(config))) => {
+                        check_shoe(hash, seed, gathered, &words.collect::<Vec<_>>(), config)
+                    }
+                };
+
+                checks.push(match result {
+                    Ok(dealt) => Check::Ok {
+                        shoe: hash.to_string(),
+                        dealt,
+                    },
+                    Err(message) => Check::Fail {
+                        shoe: hash.to_string(),
+                        message,
+                    },
+                });
+
+                revealed.insert(hash.to_string());
+            }
+            (None, _) => (),
+            _ => checks.push(Check::Fail {
+                shoe: format!("line {}", number + 1),
+                message: "unrecognised entry".to_string(),
+            }),
+        }
+    }
+
+    for (hash, _) in committed {
+        if !revealed.contains(&hash) {
+            checks.push(Check::Open { shoe: hash });
+        }
+    }
+
+    Ok(checks)
+}
+
+fn check_shoe(
+    hash: &str,
+    seed: Seed,
+    gathered: &str,
+    dealt: &[&str],
+    config: &Config,
+) -> Result<usize, String> {
+    let order: Vec<String> = gathered.split(',').map(str::to_string).collect();
+    let cards = order
+        .iter()
+        .map(|code| Card::from_code(code).ok_or(format!("unknown card `{}` gathered", code)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if !fits_in(&cards, &Card::generate_custom_deck(&config.composition)) {
+        return Err("gathered cards are not from the shoe's decks".to_string());
+    }
+
+    let mut cards = Shoe::build(cards, &config.shuffle, seed);
+
+    if commitment(&seed, &order, &cards) != hash {
+        return Err("seed does not match the commitment".to_string());
+    }
+
+    // The burn card comes off the top before anything is dealt.
+    cards.pop();
+
+    for (position, code) in dealt.iter().enumerate() {
+        match cards.pop() {
+            Some(card) if card.code() == *code => (),
+            Some(card) => {
+                return Err(format!(
+                    "card {} was {} but the shoe had {}",
+                    position + 1,
+                    code,
+                    card.code()
+                ))
+            }
+            None => return Err(format!("card {} was dealt past the end", position + 1)),
+        }
+    }
+
+    Ok(dealt.len())
+}
+
+/// Whether every card in `cards` can be taken from `shoe`, each one at most as often as it is
+/// there.
+fn fits_in(cards: &[Card], shoe: &[Card]) -> bool {
+    let mut left: HashMap<String, usize> = HashMap::new();
+
+    for card in shoe {
+        *left.entry(card.code()).or_default() += 1;
+    }
+
+    cards.iter().all(|card| match left.get_mut(&card.code()) {
+        Some(count) if *count > 0 => {
+            *count -= 1;
+            true
+        }
+        _ => false,
+    })
+}
+
+/// The start of a hash, enough to tell shoes apart.
+pub fn short(hash: &str) -> &str {
+    // By characters, as a hash read from a shared log might not be ASCII.
+    hash.char_indices()
+        .nth(16)
+        .map_or(hash, |(end, _)| &hash[..end])
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn from_hex(text: &str) -> Option<Seed> {
+    let mut seed = [0; 32];
+
+    if text.len() != seed.len() * 2 || !text.is_ascii() {
+        return None;
+    }
+
+    for (index, byte) in seed.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&text[index * 2..index * 2 + 2], 16).ok()?;
+    }
+
+    Some(seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A log file of its own for each test, so they can run side by side.
+    fn log_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!(
+            "blackjack-fair-{}-{}.log",
+            name,
+            std::process::id()
+        ))
+    }
+
+    fn verify_text(name: &str, text: &str) -> Vec<Check> {
+        let path = log_path(name);
+        fs::write(&path, text).unwrap();
+
+        let checks = verify(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        checks
+    }
+
+    /// Plays out a shoe and a few cards of the next, and returns the finished log.
+    fn played_log(name: &str) -> String {
+        let config = Config::default();
+        let path = log_path(name);
+        let mut log = FairLog::open(&path, &config).unwrap();
+        let mut shoe = Shoe::new(config.composition.clone(), config.shuffle.clone());
+
+        log.sync(&shoe).unwrap();
+
+        for _ in 0..shoe.cards_remaining() + 10 {
+            let mut card = vec![shoe.deal()];
+            log.sync(&shoe).unwrap();
+            shoe.discard(&mut card);
+        }
+
+        log.finish(&shoe).unwrap();
+
+        let text = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        text
+    }
+
+    #[test]
+    fn verifies_a_played_log() {
+        let checks = verify_text("played", &played_log("played-source"));
+
+        assert_eq!(checks.len(), 2);
+        assert!(checks.iter().all(|check| matches!(check, Check::Ok { .. })));
+        assert!(matches!(checks[1], Check::Ok { dealt: 10, .. }));
+    }
+
+    #[test]
+    fn catches_a_changed_card() {
+        let log = played_log("changed-source");
+        let reveal = log.lines().find(|line| line.starts_with("reveal")).unwrap();
+        let (start, last) = reveal.rsplit_once(' ').unwrap();
+        let changed = if last == "2H" { "3H" } else { "2H" };
+
+        let checks = verify_text(
+            "changed",
+            &log.replacen(reveal, &format!("{} {}", start, changed), 1),
+        );
+
+        assert!(matches!(checks[0], Check::Fail { .. }));
+        assert!(matches!(checks[1], Check::Ok { .. }));
+    }
+
+    #[test]
+    fn reports_malformed_lines() {
+        let hash = "ab".repeat(32);
+        let checks = verify_text(
+            "malformed",
+            &format!(
+                "nonsense\ncommit {hash}\nreveal {hash} nothex\nreveal {other} {seed} 2H 2H\n",
+                other = "cd".repeat(32),
+                seed = "00".repeat(32),
+            ),
+        );
+
+        assert_eq!(
+            checks,
+            vec![
+                Check::Fail {
+                    shoe: "line 1".to_string(),
+                    message: "unrecognised entry".to_string()
+                },
+                Check::Fail {
+                    shoe: hash.clone(),
+                    message: "invalid seed".to_string()
+                },
+                Check::Fail {
+                    shoe: "cd".repeat(32),
+                    message: "revealed without an earlier commitment".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn shortens_non_ascii_hashes() {
+        assert_eq!(short("aaaaaaaaaaaaaaaé"), "aaaaaaaaaaaaaaaé");
+        assert_eq!(short("aaaaaaaaaaaaaaaéé"), "aaaaaaaaaaaaaaaé");
+        assert_eq!(short("abc"), "abc");
+
+        let checks = verify_text("non-ascii", "commit aaaaaaaaaaaaaaaé decks=2\n");
+
+        assert_eq!(
+            checks,
+            vec![Check::Open {
+                shoe: "aaaaaaaaaaaaaaaé".to_string()
+            }]
+        );
+    }
+}
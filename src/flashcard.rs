@@ -0,0 +1,144 @@
+use rand::{distributions::WeightedIndex, prelude::Distribution, rngs::StdRng, SeedableRng};
+
+use crate::accuracy::Accuracy;
+use crate::card::Card;
+use crate::chart::Chart;
+use crate::config::Config;
+use crate::money::Money;
+use crate::strategy::{card_of_value, card_value, Action, BasicStrategy, Hint, Situation};
+
+/// Extra weight a spot gets for every time it was played wrong.
+const MISTAKE_WEIGHT: f64 = 4.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Phase {
+    Choosing,
+    Answered { played: Action, correct: bool },
+}
+
+/// Basic strategy flashcards: two card hands against an up-card, picked more often the more
+/// they have been got wrong, and graded against the strategy engine or the chart in use.
+pub struct Flashcards {
+    rng: StdRng,
+    strategy: BasicStrategy,
+    chart: Option<Chart>,
+    spots: Vec<(Situation, usize)>,
+    hand: Vec<Card>,
+    up_card: Vec<Card>,
+    phase: Phase,
+    accuracy: Accuracy,
+}
+
+impl Flashcards {
+    pub fn new(config: &Config) -> Self {
+        let mut spots = Vec::new();
+
+        for up in 1..=10 {
+            spots.extend((5..=19).map(|total| (Situation::Hard(total), up)));
+            spots.extend((13..=20).map(|total| (Situation::Soft(total), up)));
+            spots.extend((1..=10).map(|value| (Situation::Pair(value), up)));
+        }
+
+        let mut flashcards = Self {
+            rng: StdRng::from_entropy(),
+            strategy: BasicStrategy::new(&config.rules, &config.composition),
+            chart: config.chart.clone(),
+            spots,
+            hand: Vec::new(),
+            up_card: Vec::new(),
+            phase: Phase::Choosing,
+            accuracy: Accuracy::default(),
+        };
+
+        flashcards.next();
+
+        flashcards
+    }
+
+    /// Picks the next spot, weighted toward the ones played wrong most.
+    pub fn next(&mut self) {
+        let mistakes = self.accuracy.mistakes();
+        let weights = self.spots.iter().map(|(situation, up)| {
+            let wrong: usize = mistakes
+                .iter()
+                .filter(|m| m.situation == *situation && m.up == *up)
+                .map(|m| m.count)
+                .sum();
+
+            1.0 + MISTAKE_WEIGHT * wrong as f64
+        });
+
+        let index = WeightedIndex::new(weights).unwrap().sample(&mut self.rng);
+        let (situation, up) = self.spots[index];
+
+        self.hand = situation.example_hand(&mut self.rng);
+        self.up_card = vec![card_of_value(up, &mut self.rng)];
+        self.phase = Phase::Choosing;
+    }
+
+    /// Grades an action, ignoring ones the rules do not allow.
+    pub fn choose(&mut self, played: Action) {
+        if self.phase != Phase::Choosing {
+            return;
+        }
+
+        let actions = self.strategy.evaluate(&self.hand, &self.up_card[0]);
+
+        if !actions.iter().any(|(action, _)| *action == played) {
+            return;
+        }
+
+        let situation = Situation::of(&self.hand, self.strategy.joker_rule());
+        let up = self.up_value();
+        let best = self.hint().action;
+
+        self.accuracy
+            .record(situation, up, played, best, &actions, Money::whole(1));
+
+        self.phase = Phase::Answered {
+            played,
+            correct: played == best,
+        };
+    }
+
+    /// Actions the rules allow for the hand showing.
+    pub fn legal_actions(&self) -> Vec<Action> {
+        self.strategy
+            .evaluate(&self.hand, &self.up_card[0])
+            .into_iter()
+            .map(|(action, _)| action)
+            .collect()
+    }
+
+    pub fn hint(&self) -> Hint {
+        match &self.chart {
+            Some(chart) => chart.hint(
+                &self.hand,
+                &self.up_card[0],
+                &self.legal_actions(),
+                self.strategy.joker_rule(),
+            ),
+            None => self.strategy.hint(&self.hand, &self.up_card[0]),
+        }
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    pub fn hand(&self) -> &[Card] {
+        &self.hand
+    }
+
+    pub fn up_card(&self) -> &[Card] {
+        &self.up_card
+    }
+
+    pub fn accuracy(&self) -> &Accuracy {
+        &self.accuracy
+    }
+
+    fn up_value(&self) -> usize {
+        card_value(self.up_card[0].rank()).unwrap_or(1)
+    }
+}
@@ -1,14 +1,50 @@
 use std::cmp::Ordering;
 
-use rand::{rngs::ThreadRng, seq::SliceRandom, thread_rng};
+use crate::accuracy::Accuracy;
+use crate::betting::BetRamp;
+use crate::card::{Card, JokerRule, RankCounts, Suit};
+use crate::chart::Chart;
+use crate::config::Config;
+use crate::count::{Counter, CountingSystem};
+use crate::money::Money;
+use crate::odds::{self, ShoeOdds};
+use crate::rules::Rules;
+use crate::shoe::Shoe;
+use crate::strategy::{best_action, card_value, Action, BasicStrategy, Hint, Situation, Strategy};
+
+pub const MIN_BET: Money = Money::whole(50);
+pub const MAX_BET: Money = Money::whole(500);
+const BET_INCREMENT: Money = Money::whole(50);
+
+/// The only plays the game deals, whatever the rules allow the strategy engine.
+const DEALT_ACTIONS: [Action; 2] = [Action::Hit, Action::Stand];
+
+/// Chips in the tray, smallest first.
+pub const CHIPS: [Money; 4] = [
+    Money::whole(5),
+    Money::whole(25),
+    Money::whole(100),
+    Money::whole(500),
+];
+
+/// The fewest chips making up a bet, largest first, and anything left that no chip covers.
+pub fn chip_stack(bet: Money) -> (Vec<(Money, usize)>, Money) {
+    let mut left = bet;
+    let mut stack = Vec::new();
+
+    for chip in CHIPS.iter().rev() {
+        let mut count = 0;
+
+        while left >= *chip {
+            left -= *chip;
+            count += 1;
+        }
 
-use crate::card::{Card, DECK_SIZE};
+        stack.push((*chip, count));
+    }
 
-const NUMBER_OF_DECKS: usize = 2;
-pub const PLAYING_DECK_SIZE: usize = DECK_SIZE * NUMBER_OF_DECKS;
-const MIN_BET: f32 = 50.0;
-const MAX_BET: f32 = 500.0;
-const BET_INCREMENT: f32 = 50.0;
+    (stack, left)
+}
 
 #[derive(PartialEq)]
 pub enum Input {
@@ -18,6 +54,10 @@ pub enum Input {
     New,
     IncreaseBet,
     DecreaseBet,
+    AddChip(Money),
+    ClearBet,
+    RepeatBet,
+    Rebuy,
 }
 
 #[derive(Clone, Copy, PartialEq)]
@@ -31,98 +71,380 @@ pub enum Winner {
 pub enum State {
     MyTurn,
     Result(Winner),
+    /// The hand is over and the balance left can't cover the table minimum.
+    Broke(Winner),
+}
+
+/// Everything the exact values of a decision are worked out from, copied out of the game so the
+/// work can go to another thread.
+pub struct ValuesQuestion {
+    /// Which decision of the game the values are for.
+    pub decision: usize,
+    my_hand: Vec<Card>,
+    dealer_up: Vec<Card>,
+    unseen: RankCounts,
+    full: RankCounts,
+    rules: Rules,
+    joker_rule: JokerRule,
+}
+
+impl ValuesQuestion {
+    /// Works the values out, which can take seconds for low totals.
+    pub fn answer(&self) -> Vec<(Action, f64)> {
+        odds::action_values(
+            &self.my_hand,
+            &self.dealer_up,
+            &self.unseen,
+            &self.full,
+            &self.rules,
+            self.joker_rule,
+        )
+    }
 }
 
 pub struct Game {
     state: State,
-    rng: ThreadRng,
-    deck: Vec<Card>,
+    shoe: Shoe,
+    rules: Rules,
+    strategy: BasicStrategy,
+    /// Played to instead of the strategy engine for hints and accuracy, when one is configured.
+    chart: Option<Chart>,
+    joker_rule: JokerRule,
     my_hand: Vec<Card>,
     dealer_hand: Vec<Card>,
-    profit: f32,
-    bet: f32,
+    profit: Money,
+    bet: Money,
+    /// The bet on the last hand dealt, to put down again.
+    last_bet: Money,
+    /// Money each buy-in brings to the table, or none for a game with no bankroll to lose.
+    buy_in: Option<Money>,
+    bought_in: Money,
+    accuracy: Accuracy,
+    counter: Counter,
+    counted_shoes: usize,
+    /// The shoe the dealer's hole card came from, as it only counts towards that shoe.
+    hole_card_shoe: usize,
+    ramp: BetRamp,
+    action_values: Option<Vec<(Action, f64)>>,
+    /// Counts the cards dealt, so values worked out for an earlier decision can be told apart.
+    decision: usize,
 }
 
 impl Game {
-    pub fn new() -> Self {
+    /// A game played with the configured bankroll, bets kept within what is left of it.
+    pub fn new(config: &Config) -> Self {
+        let buy_in = config.bankroll.amount;
+
+        Self {
+            buy_in: Some(buy_in),
+            bought_in: buy_in,
+            ..Self::with_shoe(
+                config,
+                Shoe::new(config.composition.clone(), config.shuffle.clone()),
+            )
+        }
+    }
+
+    /// A game dealt from a seeded shoe, for simulations. The same seed deals the same cards,
+    /// and there is no balance to run out of, simulations keeping track of their own.
+    pub fn with_seed(config: &Config, seed: u64) -> Self {
+        Self::with_shoe(
+            config,
+            Shoe::seeded(config.composition.clone(), config.shuffle.clone(), seed),
+        )
+    }
+
+    fn with_shoe(config: &Config, shoe: Shoe) -> Self {
         Self {
             state: State::Result(Winner::None),
-            rng: thread_rng(),
-            deck: Vec::new(),
+            // Only hit and stand are dealt, so hints and exact values leave the rest out.
+            rules: config.rules.dealt(),
+            strategy: BasicStrategy::new(&config.rules.dealt(), &config.composition),
+            chart: config.chart.clone(),
+            joker_rule: config.composition.joker_rule,
+            shoe,
             my_hand: Vec::new(),
             dealer_hand: Vec::new(),
-            profit: 0.0,
+            profit: Money::ZERO,
             bet: MIN_BET,
+            last_bet: MIN_BET,
+            buy_in: None,
+            bought_in: Money::ZERO,
+            accuracy: Accuracy::default(),
+            counter: Counter::new(
+                Box::new(config.counting_system.clone()),
+                config.composition.number_of_decks,
+                config.composition.deck_size(),
+            ),
+            counted_shoes: 0,
+            hole_card_shoe: 0,
+            ramp: config.ramp.clone(),
+            action_values: None,
+            decision: 0,
         }
     }
 
     pub fn update(&mut self, input: Input) {
         match self.state {
             State::MyTurn => match input {
-                Input::Hit => self.hit(),
-                Input::Stay => self.calculate_result(),
+                Input::Hit => {
+                    self.check_decision(Action::Hit);
+                    self.hit();
+                }
+                Input::Stay => {
+                    self.check_decision(Action::Stand);
+                    self.calculate_result();
+                }
                 _ => (),
             },
             State::Result(_) => match input {
-                Input::New => self.new_deck(),
+                Input::New if self.bet >= MIN_BET => self.new_deck(),
                 Input::IncreaseBet => {
-                    self.bet += BET_INCREMENT;
-                    self.bet = self.bet.clamp(MIN_BET, MAX_BET);
+                    let advised = self.advised_bet();
+
+                    if self.ramp.jump && self.bet < advised {
+                        self.bet = advised;
+                    } else {
+                        self.bet += BET_INCREMENT;
+                    }
+                    self.bet = self.within_limits(self.bet);
                 }
                 Input::DecreaseBet => {
-                    self.bet -= BET_INCREMENT;
-                    self.bet = self.bet.clamp(MIN_BET, MAX_BET);
+                    let advised = self.advised_bet();
+
+                    if self.ramp.jump && self.bet > advised {
+                        self.bet = advised;
+                    } else {
+                        self.bet -= BET_INCREMENT;
+                    }
+                    self.bet = self.within_limits(self.bet);
                 }
+                // A chip that would take the bet over the limit stays in the tray.
+                Input::AddChip(chip) if self.bet + chip <= self.max_bet() => self.bet += chip,
+                Input::ClearBet => self.bet = Money::ZERO,
+                Input::RepeatBet => self.bet = self.last_bet.min(self.max_bet()),
                 _ => (),
             },
+            State::Broke(winner) => {
+                if let (Input::Rebuy, Some(buy_in)) = (input, self.buy_in) {
+                    self.bought_in += buy_in;
+                    self.state = State::Result(winner);
+                    self.bet = self.within_limits(self.bet);
+                }
+            }
+        }
+    }
+
+    /// Deals a hand and plays it out with a strategy choosing between hit and stand, the only
+    /// plays the game deals, returning the money won or lost.
+    pub fn play_round(&mut self, strategy: &dyn Strategy) -> Money {
+        self.update(Input::New);
+
+        while let (State::MyTurn, Some(up_card)) = (self.state, self.up_card()) {
+            let input = match strategy.play(&self.my_hand, up_card, self.true_count()) {
+                Action::Hit => Input::Hit,
+                _ => Input::Stay,
+            };
+
+            self.update(input);
+        }
+
+        match self.state {
+            State::Result(winner) | State::Broke(winner) => self.payout(winner) - self.bet,
+            State::MyTurn => Money::ZERO,
+        }
+    }
+
+    /// Sets the bet for the next hand, kept within the table limits and the balance.
+    pub fn set_bet(&mut self, bet: Money) {
+        self.bet = self.within_limits(bet);
+    }
+
+    /// Puts down an exact bet for the next hand, if it is within the table limits and the
+    /// balance.
+    pub fn place_bet(&mut self, bet: Money) -> Result<(), String> {
+        if !matches!(self.state, State::Result(_)) {
+            return Err("bets go down between hands".to_string());
+        }
+
+        if bet < MIN_BET {
+            return Err(format!("the table minimum is {}", MIN_BET));
+        }
+
+        if bet > MAX_BET {
+            return Err(format!("the table maximum is {}", MAX_BET));
+        }
+
+        if let Some(balance) = self.balance().filter(|balance| bet > *balance) {
+            return Err(format!("only {} left to bet", balance));
+        }
+
+        self.bet = bet;
+
+        Ok(())
+    }
+
+    /// The most that can go on the next hand: the table maximum, or the balance if that is
+    /// less.
+    fn max_bet(&self) -> Money {
+        match self.balance() {
+            Some(balance) => balance.min(MAX_BET),
+            None => MAX_BET,
         }
     }
 
+    /// A bet raised to the table minimum, then cut to what can go on the hand. A balance under
+    /// the minimum leaves it short, so it can't be dealt.
+    fn within_limits(&self, bet: Money) -> Money {
+        bet.max(MIN_BET).min(self.max_bet())
+    }
+
+    /// Changes the bet ramp advised bets come from.
+    pub fn set_ramp(&mut self, ramp: BetRamp) {
+        self.ramp = ramp;
+    }
+
+    fn check_decision(&mut self, played: Action) {
+        let Some(up_card) = self.dealer_hand.get(1) else {
+            return;
+        };
+
+        // Graded on the plays there are keys for, so a hand the rules would double or split
+        // isn't a mistake the player couldn't avoid.
+        let actions: Vec<(Action, f64)> = self
+            .strategy
+            .evaluate(&self.my_hand, up_card)
+            .into_iter()
+            .filter(|(action, _)| DEALT_ACTIONS.contains(action))
+            .collect();
+        let best = match &self.chart {
+            Some(chart) => chart.play(&self.my_hand, up_card, &DEALT_ACTIONS, self.joker_rule),
+            None => best_action(&actions),
+        };
+
+        self.accuracy.record(
+            Situation::of(&self.my_hand, self.joker_rule),
+            card_value(up_card.rank()).unwrap_or(1),
+            played,
+            best,
+            &actions,
+            self.bet,
+        );
+    }
+
     fn new_deck(&mut self) {
         self.state = State::MyTurn;
 
-        if self.deck.len() < DECK_SIZE / 2 {
-            self.deck.clear();
-            self.deck.append(&mut Card::generate_deck(NUMBER_OF_DECKS));
-            self.deck.shuffle(&mut self.rng);
+        self.shoe.discard(&mut self.my_hand);
+        self.shoe.discard(&mut self.dealer_hand);
+
+        if self.shoe.cards_remaining() < self.shoe.size() / 4 {
+            self.shoe.shuffle();
+            self.follow_shuffle();
         }
 
         self.profit -= self.bet;
+        self.last_bet = self.bet;
+
+        let card = self.deal(true);
+        self.my_hand.push(card);
+        let card = self.deal(false);
+        self.dealer_hand.push(card);
+        let card = self.deal(true);
+        self.my_hand.push(card);
+        let card = self.deal(true);
+        self.dealer_hand.push(card);
+
+        if Self::hand_value(&self.my_hand, self.joker_rule) == 21 {
+            self.calculate_result();
+        }
+    }
 
-        self.my_hand.clear();
-        self.dealer_hand.clear();
+    /// Deals the next card, counting it straight away unless it goes face down.
+    fn deal(&mut self, face_up: bool) -> Card {
+        let mut card = self.shoe.deal();
+        self.action_values = None;
+        self.decision += 1;
+        self.follow_shuffle();
 
-        self.my_hand.push(self.deck.pop().unwrap());
-        self.dealer_hand.push(self.deck.pop().unwrap());
-        self.my_hand.push(self.deck.pop().unwrap());
-        self.dealer_hand.push(self.deck.pop().unwrap());
+        if face_up {
+            self.counter.see(&card);
+        } else {
+            card.hide();
+            self.hole_card_shoe = self.shoe.shuffles();
+        }
 
-        self.dealer_hand[0].hide();
+        card
+    }
 
-        if Self::hand_value(&self.my_hand) == 21 {
-            self.calculate_result();
+    /// Every shuffle starts a new shoe and a new count, from the moment the shoe is shuffled,
+    /// even when it runs out in the middle of a hand.
+    fn follow_shuffle(&mut self) {
+        if self.shoe.shuffles() != self.counted_shoes {
+            self.counted_shoes = self.shoe.shuffles();
+            self.counter.reset();
         }
     }
 
     fn hit(&mut self) {
-        self.my_hand.push(self.deck.pop().unwrap());
+        let card = self.deal(true);
+        self.my_hand.push(card);
 
-        if Self::hand_value(&self.my_hand) > 20 || self.my_hand.len() == 5 {
+        if Self::hand_value(&self.my_hand, self.joker_rule) > 20
+            || self.rules.charlie == Some(self.my_hand.len())
+        {
             self.calculate_result();
         }
     }
 
     fn calculate_result(&mut self) {
-        self.state = State::Result(Winner::None);
-
         self.dealer_hand[0].show();
 
-        while Self::hand_value(&self.dealer_hand) < 17 && self.dealer_hand.len() < 5 {
-            self.dealer_hand.push(self.deck.pop().unwrap());
+        // A hole card from before a reshuffle belongs to the old shoe's count.
+        if self.hole_card_shoe == self.shoe.shuffles() {
+            self.counter.see(&self.dealer_hand[0]);
         }
 
-        let mut my_hand_value = Self::hand_value(&self.my_hand);
-        let mut dealer_hand_value = Self::hand_value(&self.dealer_hand);
+        while self.dealer_draws() {
+            let card = self.deal(true);
+            self.dealer_hand.push(card);
+        }
+
+        let winner = Self::winner(
+            &self.my_hand,
+            &self.dealer_hand,
+            &self.rules,
+            self.joker_rule,
+        );
+        self.profit += self.payout(winner);
+
+        match self.balance() {
+            Some(balance) if balance < MIN_BET => self.state = State::Broke(winner),
+            _ => {
+                self.state = State::Result(winner);
+                self.bet = self.bet.min(self.max_bet());
+            }
+        }
+    }
+
+    /// Money paid back on the bet, stake included.
+    fn payout(&self, winner: Winner) -> Money {
+        match winner {
+            Winner::Me => self.bet + self.bet.scale(self.rules.win_pays),
+            Winner::None => self.bet,
+            Winner::Dealer => Money::ZERO,
+        }
+    }
+
+    /// Who takes a finished hand, `Winner::None` being a push.
+    pub fn winner(
+        my_hand: &[Card],
+        dealer_hand: &[Card],
+        rules: &Rules,
+        joker_rule: JokerRule,
+    ) -> Winner {
+        let mut my_hand_value = Self::hand_value(my_hand, joker_rule);
+        let mut dealer_hand_value = Self::hand_value(dealer_hand, joker_rule);
 
         if my_hand_value > 21 {
             my_hand_value = -1;
@@ -132,38 +454,72 @@ impl Game {
             dealer_hand_value = -1;
         }
 
-        let my_hand_length = self.my_hand.len();
-        let dealer_hand_length = self.dealer_hand.len();
+        let my_charlie = rules.charlie == Some(my_hand.len()) && my_hand_value != -1;
+        let dealer_charlie = rules.charlie == Some(dealer_hand.len()) && dealer_hand_value != -1;
 
-        if my_hand_length == 5 && my_hand_value != -1 {
-            if dealer_hand_length == 5 && dealer_hand_value != -1 {
-                self.profit += self.bet;
+        if my_charlie {
+            if dealer_charlie {
+                Winner::None
             } else {
-                self.state = State::Result(Winner::Me);
-                self.profit += self.bet * 1.5;
+                Winner::Me
             }
-        } else if dealer_hand_length == 5 && dealer_hand_value != -1 {
-            self.state = State::Result(Winner::Dealer);
+        } else if dealer_charlie || (my_hand_value == -1 && !rules.bust_push) {
+            Winner::Dealer
         } else {
             match my_hand_value.cmp(&dealer_hand_value) {
-                Ordering::Equal => {
-                    self.state = State::Result(Winner::None);
-                    self.profit += self.bet;
-                }
-                Ordering::Greater => {
-                    self.state = State::Result(Winner::Me);
-                    self.profit += self.bet * 1.5;
-                }
-                Ordering::Less => self.state = State::Result(Winner::Dealer),
+                Ordering::Equal => Winner::None,
+                Ordering::Greater => Winner::Me,
+                Ordering::Less => Winner::Dealer,
             }
         }
     }
 
-    fn hand_value(hand: &Vec<Card>) -> i32 {
+    fn dealer_draws(&self) -> bool {
+        Self::dealer_should_draw(&self.dealer_hand, &self.rules, self.joker_rule)
+    }
+
+    /// Whether the dealer takes another card: below 17, or on a soft 17 when the rules say so,
+    /// and never once a charlie is reached.
+    pub fn dealer_should_draw(hand: &[Card], rules: &Rules, joker_rule: JokerRule) -> bool {
+        if rules.charlie == Some(hand.len()) {
+            return false;
+        }
+
+        let value = Self::hand_value(hand, joker_rule);
+
+        value < 17 || (value == 17 && rules.dealer_hits_soft_17 && Self::is_soft(hand, joker_rule))
+    }
+
+    /// Whether an ace or wild joker is counting for more than 1 in the hand's value.
+    pub fn is_soft(hand: &[Card], joker_rule: JokerRule) -> bool {
+        let low_value: i32 = hand
+            .iter()
+            .map(|card| match (card.is_ace(), card.is_joker(), joker_rule) {
+                (true, _, _) => 1,
+                (_, true, JokerRule::Wild) => 1,
+                (_, true, JokerRule::Fixed(joker_value)) => joker_value,
+                _ => card.value(),
+            })
+            .sum();
+
+        Self::hand_value(hand, joker_rule) > low_value
+    }
+
+    pub fn hand_value(hand: &[Card], joker_rule: JokerRule) -> i32 {
         let mut value = 0;
         let mut aces = 0;
+        let mut wild_jokers = 0;
 
         for card in hand {
+            if card.is_joker() {
+                match joker_rule {
+                    JokerRule::Wild => wild_jokers += 1,
+                    JokerRule::Fixed(joker_value) => value += joker_value,
+                }
+
+                continue;
+            }
+
             value += card.value();
 
             if card.is_ace() {
@@ -171,6 +527,15 @@ impl Game {
             };
         }
 
+        if wild_jokers > 0 {
+            // Count aces and wild jokers as 1, then fill towards 21. Each joker can add any
+            // amount up to 10 and each ace exactly 10, so anything up to the total is reachable.
+            let low_value = value - aces * 10 + wild_jokers;
+            let room = (21 - low_value).max(0);
+
+            return low_value + room.min((wild_jokers + aces) * 10);
+        }
+
         while value > 21 && aces != 0 {
             value -= 10;
             aces -= 1;
@@ -187,18 +552,175 @@ impl Game {
         &self.dealer_hand
     }
 
-    pub fn bet(&self) -> f32 {
+    /// The dealer's face up card, once cards are dealt.
+    pub fn up_card(&self) -> Option<&Card> {
+        self.dealer_hand.get(1)
+    }
+
+    /// The hand and up-card value being decided on, while it is the player's turn.
+    pub fn spot(&self) -> Option<(Situation, usize)> {
+        match (self.state, self.up_card()) {
+            (State::MyTurn, Some(up_card)) => Some((
+                Situation::of(&self.my_hand, self.joker_rule),
+                card_value(up_card.rank()).unwrap_or(1),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Basic strategy, or the chart's play, between hit and stand for the current hand while
+    /// it is the player's turn.
+    pub fn hint(&self) -> Option<Hint> {
+        let (State::MyTurn, Some(up_card)) = (self.state, self.up_card()) else {
+            return None;
+        };
+
+        Some(match &self.chart {
+            Some(chart) => chart.hint(&self.my_hand, up_card, &DEALT_ACTIONS, self.joker_rule),
+            None => self.strategy.hint(&self.my_hand, up_card),
+        })
+    }
+
+    /// Exact dealer outcomes and chances for standing or hitting, on the player's turn.
+    pub fn odds(&self) -> Option<ShoeOdds> {
+        if self.state != State::MyTurn {
+            return None;
+        }
+
+        let (unseen, dealer_up) = self.unseen();
+
+        Some(ShoeOdds::new(
+            &self.my_hand,
+            &dealer_up,
+            &unseen,
+            &self.shoe.full_counts(),
+            &self.rules,
+            self.joker_rule,
+        ))
+    }
+
+    /// Exact expected value of every legal action from the cards left, on the player's turn,
+    /// once it has been worked out.
+    pub fn action_values(&self) -> Option<&[(Action, f64)]> {
+        if self.state != State::MyTurn {
+            return None;
+        }
+
+        self.action_values.as_deref()
+    }
+
+    /// What to work out the exact values of the current decision from, on the player's turn
+    /// while they are not known yet.
+    pub fn values_question(&self) -> Option<ValuesQuestion> {
+        if self.state != State::MyTurn || self.action_values.is_some() {
+            return None;
+        }
+
+        let (unseen, dealer_up) = self.unseen();
+
+        Some(ValuesQuestion {
+            decision: self.decision,
+            my_hand: self
+                .my_hand
+                .iter()
+                .map(|card| Card::new(Suit::Spade, card.rank()))
+                .collect(),
+            dealer_up,
+            unseen,
+            full: self.shoe.full_counts(),
+            rules: self.rules.clone(),
+            joker_rule: self.joker_rule,
+        })
+    }
+
+    /// Keeps the exact values worked out for a decision, unless play has moved on from it.
+    pub fn answer_values(&mut self, decision: usize, values: Vec<(Action, f64)>) {
+        if decision == self.decision {
+            self.action_values = Some(values);
+        }
+    }
+
+    /// Counts of the cards the player has not seen, the dealer's hole card included, and the
+    /// dealer's face up cards.
+    fn unseen(&self) -> (RankCounts, Vec<Card>) {
+        let mut unseen = self.shoe.unseen_counts();
+        let mut dealer_up = Vec::new();
+
+        for card in &self.dealer_hand {
+            if card.is_hidden() {
+                unseen[card.rank().index()] += 1;
+            } else {
+                dealer_up.push(Card::new(Suit::Spade, card.rank()));
+            }
+        }
+
+        (unseen, dealer_up)
+    }
+
+    pub fn bet(&self) -> Money {
         self.bet
     }
 
-    pub fn deck_length(&self) -> usize {
-        self.deck.len()
+    /// Whether the bet is too small to deal the next hand on.
+    pub fn is_under_minimum(&self) -> bool {
+        self.bet < MIN_BET
+    }
+
+    pub fn last_bet(&self) -> Money {
+        self.last_bet
+    }
+
+    /// The bet the ramp gives for the true count, to the cent, within the table limits.
+    pub fn advised_bet(&self) -> Money {
+        self.ramp.bet(self.true_count()).clamp(MIN_BET, MAX_BET)
+    }
+
+    /// Whether the bet is far enough from the ramp to point out.
+    pub fn is_off_ramp(&self) -> bool {
+        BetRamp::is_far_off(self.bet, self.advised_bet())
+    }
+
+    pub fn shoe(&self) -> &Shoe {
+        &self.shoe
     }
 
-    pub fn profit(&self) -> f32 {
+    pub fn profit(&self) -> Money {
         self.profit
     }
 
+    /// Money left from the buy-ins, the bet on a hand in play taken out, if there is a
+    /// bankroll.
+    pub fn balance(&self) -> Option<Money> {
+        self.buy_in.map(|_| self.bought_in + self.profit)
+    }
+
+    pub fn counting_system(&self) -> &dyn CountingSystem {
+        self.counter.system()
+    }
+
+    pub fn running_count(&self) -> f32 {
+        self.counter.running()
+    }
+
+    /// Running count an unbalanced system starts raising bets at.
+    pub fn key_count(&self) -> Option<f32> {
+        self.counter.key_count()
+    }
+
+    pub fn true_count(&self) -> f32 {
+        let cards_remaining = self.shoe.cards_remaining();
+        self.counter
+            .true_count(self.shoe.size() - cards_remaining, cards_remaining)
+    }
+
+    pub fn accuracy(&self) -> &Accuracy {
+        &self.accuracy
+    }
+
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
     pub fn state(&self) -> State {
         self.state
     }
@@ -0,0 +1,19 @@
+pub mod accuracy;
+pub mod bankroll;
+pub mod betting;
+pub mod card;
+pub mod chart;
+pub mod config;
+pub mod count;
+pub mod countdown;
+pub mod deviation;
+pub mod fair;
+pub mod flashcard;
+pub mod game;
+pub mod money;
+pub mod odds;
+pub mod rules;
+pub mod shoe;
+pub mod shuffle;
+pub mod simulate;
+pub mod strategy;
@@ -1,36 +1,630 @@
+use std::{
+    env,
+    num::NonZeroUsize,
+    path::Path,
+    process::ExitCode,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
 use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
 
-mod card;
-mod game;
-use game::{Game, Input};
+use blackjack::config::{Config, CONFIG_PATH};
+use blackjack::countdown::{self, Countdown};
+use blackjack::deviation::{self, DeviationTrainer};
+use blackjack::fair::{self, Check, FairLog, FAIR_LOG_PATH};
+use blackjack::flashcard::{self, Flashcards};
+use blackjack::game::{Game, Input, State, CHIPS};
+use blackjack::money::Money;
+use blackjack::simulate::{Betting, Contender, Player, Results, Simulation};
+use blackjack::strategy::Action;
+
 mod tui;
-use tui::TUI;
+use tui::{BetEntry, TUI};
+
+/// How often a screen waiting on work from another thread redraws to show how it is going.
+const WORKING_REDRAW: Duration = Duration::from_millis(100);
+
+/// Exact values being worked out on another thread, so the table keeps drawing and taking keys
+/// while a low total takes seconds.
+struct ValuesWork {
+    decision: usize,
+    started: Instant,
+    worker: JoinHandle<Vec<(Action, f64)>>,
+}
+
+fn main() -> std::io::Result<ExitCode> {
+    let config = Config::load(Path::new(CONFIG_PATH))?;
+    let args: Vec<String> = env::args().skip(1).collect();
 
-fn main() -> std::io::Result<()> {
-    let mut tui = TUI::new();
-    let mut game = Game::new();
+    match args.first().map(String::as_str) {
+        None => play(&config),
+        Some("countdown") => countdown(&config),
+        Some("deviations") => deviations(&config),
+        Some("flashcards") => flashcards(&config),
+        Some("simulate") => simulate(&config, &args[1..]),
+        Some("risk") => risk(&config, &args[1..]),
+        Some("verify") => {
+            let path = args.get(1).map_or(FAIR_LOG_PATH, String::as_str);
+
+            verify(Path::new(path))
+        }
+        Some(command) => {
+            eprintln!(
+                "unknown command `{}`, expected `verify [log]`, `countdown`, `deviations`, `flashcards`, `simulate` or `risk`",
+                command
+            );
+            Ok(ExitCode::FAILURE)
+        }
+    }
+}
+
+fn play(config: &Config) -> std::io::Result<ExitCode> {
+    let mut fair_log = FairLog::open(Path::new(FAIR_LOG_PATH), config)?;
+
+    let mut tui = TUI::new(config);
+    let mut game = Game::new(config);
+    let mut bet_entry: Option<BetEntry> = None;
+
+    let mut values_work: Option<ValuesWork> = None;
+
+    fair_log.sync(game.shoe())?;
 
     loop {
         let mut input = Input::None;
 
-        if let Event::Key(key) = event::read()? {
-            if key.kind == KeyEventKind::Press {
+        if let Some(Event::Key(key)) = next_event(values_work.is_some())? {
+            if key.kind == KeyEventKind::Press && tui.showing_report() {
+                // The session report comes up on the first quit, the second one ends it.
                 match key.code {
                     KeyCode::Char('q') => break,
+                    _ => tui.toggle_report(),
+                }
+            } else if let (KeyEventKind::Press, Some(entry)) = (key.kind, &mut bet_entry) {
+                match key.code {
+                    KeyCode::Char(c) if c.is_ascii_digit() || "$,.".contains(c) => {
+                        entry.typed.push(c)
+                    }
+                    KeyCode::Backspace => {
+                        entry.typed.pop();
+                    }
+                    KeyCode::Enter => {
+                        match entry
+                            .typed
+                            .parse::<Money>()
+                            .and_then(|bet| game.place_bet(bet))
+                        {
+                            Ok(()) => bet_entry = None,
+                            Err(error) => entry.error = Some(error),
+                        }
+                    }
+                    KeyCode::Esc => bet_entry = None,
+                    _ => (),
+                }
+            } else if key.kind == KeyEventKind::Press {
+                match key.code {
+                    KeyCode::Char('q') => tui.toggle_report(),
+                    KeyCode::Char('b') if matches!(game.state(), State::Result(_)) => {
+                        bet_entry = Some(BetEntry::default())
+                    }
                     KeyCode::Char('h') => input = Input::Hit,
                     KeyCode::Char('s') => input = Input::Stay,
                     KeyCode::Char('n') => input = Input::New,
                     KeyCode::Char('i') => input = Input::IncreaseBet,
                     KeyCode::Char('d') => input = Input::DecreaseBet,
+                    KeyCode::Char('r') => input = Input::Rebuy,
+                    KeyCode::Char('x') => input = Input::ClearBet,
+                    KeyCode::Char('l') => input = Input::RepeatBet,
+                    KeyCode::Char(key @ '1'..='4') => {
+                        input = Input::AddChip(CHIPS[key as usize - '1' as usize])
+                    }
+                    KeyCode::Char('?') => tui.request_hint(),
+                    KeyCode::Char('c') => tui.toggle_count(),
+                    KeyCode::Char('o') => tui.toggle_odds(),
+                    KeyCode::Char('e') => tui.toggle_values(),
+                    KeyCode::Char('v') => tui.toggle_chart(),
                     _ => (),
                 }
             }
         }
 
+        if input != Input::None {
+            tui.clear_hint();
+        }
+
         game.update(input);
-        tui.draw(&game)?;
+        // A reshuffle is committed to before any card from the new shoe shows.
+        fair_log.sync(game.shoe())?;
+        values_work = follow_values(&mut game, values_work, tui.showing_values());
+        tui.draw(
+            &game,
+            bet_entry.as_ref(),
+            values_work.as_ref().map(|work| work.started.elapsed()),
+        )?;
     }
 
     ratatui::restore();
-    Ok(())
+    fair_log.finish(game.shoe())?;
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// The next terminal event, or none if `working` and nothing happens before the next redraw.
+fn next_event(working: bool) -> std::io::Result<Option<Event>> {
+    if working && !event::poll(WORKING_REDRAW)? {
+        return Ok(None);
+    }
+
+    event::read().map(Some)
+}
+
+/// Hands finished exact values to the game and starts on the decision showing if they are
+/// wanted. Work for a decision play has moved on from is left to finish unheard.
+fn follow_values(game: &mut Game, work: Option<ValuesWork>, wanted: bool) -> Option<ValuesWork> {
+    if let Some(work) = work {
+        if work.worker.is_finished() {
+            let values = work
+                .worker
+                .join()
+                .expect("the exact values thread panicked");
+            game.answer_values(work.decision, values);
+        } else if game.values_question().map(|question| question.decision) == Some(work.decision) {
+            return Some(work);
+        }
+    }
+
+    let question = game.values_question().filter(|_| wanted)?;
+
+    Some(ValuesWork {
+        decision: question.decision,
+        started: Instant::now(),
+        worker: thread::spawn(move || question.answer()),
+    })
+}
+
+/// Prints how every shoe in a fair log checked out, failing if any did not.
+fn verify(path: &Path) -> std::io::Result<ExitCode> {
+    let mut all_ok = true;
+
+    for check in fair::verify(path)? {
+        match check {
+            Check::Ok { shoe, dealt } => {
+                println!("ok    {}  {} cards dealt", fair::short(&shoe), dealt)
+            }
+            Check::Fail { shoe, message } => {
+                println!("FAIL  {}  {}", fair::short(&shoe), message);
+                all_ok = false;
+            }
+            Check::Open { shoe } => {
+                println!("open  {}  seed not revealed yet", fair::short(&shoe))
+            }
+        }
+    }
+
+    Ok(if all_ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+fn countdown(config: &Config) -> std::io::Result<ExitCode> {
+    let mut tui = TUI::new(config);
+    let mut countdown = Countdown::new(config);
+    let mut typed = String::new();
+
+    loop {
+        tui.draw_countdown(&countdown, &typed)?;
+
+        // Only wait for a key until the next cards are due.
+        if let Some(timeout) = countdown.time_to_next_deal() {
+            if !event::poll(timeout)? {
+                countdown.update();
+                continue;
+            }
+        }
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match (countdown.phase(), key.code) {
+            (_, KeyCode::Char('q')) => break,
+            (countdown::Phase::Dealing, KeyCode::Char(' ')) => countdown.deal(),
+            (countdown::Phase::Answering, KeyCode::Char(c))
+                if c.is_ascii_digit() || c == '-' || c == '.' =>
+            {
+                typed.push(c)
+            }
+            (countdown::Phase::Answering, KeyCode::Backspace) => {
+                typed.pop();
+            }
+            (countdown::Phase::Answering, KeyCode::Enter) => {
+                if let Ok(answer) = typed.parse() {
+                    countdown.answer(answer);
+                }
+                typed.clear();
+            }
+            (countdown::Phase::Done, KeyCode::Char('n')) => countdown.start(),
+            _ => (),
+        }
+    }
+
+    ratatui::restore();
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn deviations(config: &Config) -> std::io::Result<ExitCode> {
+    if config.deviations.is_empty() {
+        eprintln!(
+            "no deviations to train, check [deviations] in {}",
+            CONFIG_PATH
+        );
+        return Ok(ExitCode::FAILURE);
+    }
+
+    let mut tui = TUI::new(config);
+    let mut trainer = DeviationTrainer::new(config.deviations.clone());
+
+    loop {
+        tui.draw_deviations(&trainer)?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        if tui.showing_report() {
+            match key.code {
+                KeyCode::Char('q') => break,
+                _ => tui.toggle_report(),
+            }
+            continue;
+        }
+
+        match (trainer.phase(), key.code) {
+            (_, KeyCode::Char('q')) => tui.toggle_report(),
+            (deviation::Phase::Choosing, KeyCode::Char(c)) => match c {
+                'h' => trainer.choose(Action::Hit),
+                's' => trainer.choose(Action::Stand),
+                'd' => trainer.choose(Action::Double),
+                'p' => trainer.choose(Action::Split),
+                'r' => trainer.choose(Action::Surrender),
+                _ => (),
+            },
+            (deviation::Phase::Answered { .. }, _) => trainer.next(),
+            _ => (),
+        }
+    }
+
+    ratatui::restore();
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn flashcards(config: &Config) -> std::io::Result<ExitCode> {
+    let mut tui = TUI::new(config);
+    let mut flashcards = Flashcards::new(config);
+
+    loop {
+        tui.draw_flashcards(&flashcards)?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        if tui.showing_report() {
+            match key.code {
+                KeyCode::Char('q') => break,
+                _ => tui.toggle_report(),
+            }
+            continue;
+        }
+
+        match (flashcards.phase(), key.code) {
+            (_, KeyCode::Char('q')) => tui.toggle_report(),
+            (flashcard::Phase::Choosing, KeyCode::Char(c)) => match c {
+                'h' => flashcards.choose(Action::Hit),
+                's' => flashcards.choose(Action::Stand),
+                'd' => flashcards.choose(Action::Double),
+                'p' => flashcards.choose(Action::Split),
+                'r' => flashcards.choose(Action::Surrender),
+                _ => (),
+            },
+            (flashcard::Phase::Answered { .. }, _) => flashcards.next(),
+            _ => (),
+        }
+    }
+
+    ratatui::restore();
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Hands simulated when `--hands` is not given.
+const DEFAULT_HANDS: u64 = 1_000_000;
+
+/// Reads the options shared by `simulate` and `risk`, printing usage if they are wrong.
+fn simulation_options(config: &Config, command: &str, args: &[String]) -> Option<Simulation> {
+    match parse_simulation(args, config) {
+        Ok(simulation) => Some(simulation),
+        Err(message) => {
+            eprintln!("{}", message);
+            eprintln!(
+                "usage: blackjack {} [--hands N] [--seed N] [--threads N] [--bankroll N] \
+                 [--paths N] [--player basic|deviations|chart] [--flat] \
+                 [--compare <player>[@flat|@<ramp>]]...",
+                command
+            );
+            None
+        }
+    }
+}
+
+fn simulate(config: &Config, args: &[String]) -> std::io::Result<ExitCode> {
+    let Some(simulation) = simulation_options(config, "simulate", args) else {
+        return Ok(ExitCode::FAILURE);
+    };
+
+    let results = simulation.run(config);
+    print_simulation(&simulation, config, &results);
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn risk(config: &Config, args: &[String]) -> std::io::Result<ExitCode> {
+    let Some(simulation) = simulation_options(config, "risk", args) else {
+        return Ok(ExitCode::FAILURE);
+    };
+
+    let mut tui = TUI::new(config);
+    let progress = Arc::new(AtomicUsize::new(0));
+
+    // Played on a thread of its own so the screen keeps drawing and `q` still quits while the
+    // hands run.
+    let worker = {
+        let simulation = simulation.clone();
+        let config = config.clone();
+        let progress = Arc::clone(&progress);
+
+        thread::spawn(move || simulation.run_with_progress(&config, &progress))
+    };
+    let mut worker = Some(worker);
+    let mut results = None;
+
+    loop {
+        if worker.as_ref().is_some_and(JoinHandle::is_finished) {
+            let worker = worker.take().expect("the simulation was running");
+            results = Some(worker.join().expect("the simulation thread panicked"));
+        }
+
+        tui.draw_risk(
+            &simulation,
+            results.as_ref(),
+            progress.load(Ordering::Relaxed),
+        )?;
+
+        if !event::poll(WORKING_REDRAW)? {
+            continue;
+        }
+
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press && key.code == KeyCode::Char('q') {
+                break;
+            }
+        }
+    }
+
+    ratatui::restore();
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn parse_simulation(args: &[String], config: &Config) -> Result<Simulation, String> {
+    let mut simulation = Simulation {
+        hands: DEFAULT_HANDS,
+        seed: rand::random(),
+        contenders: Vec::new(),
+        threads: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        bankroll: config.bankroll.clone(),
+    };
+    // A configured chart is what the player goes by at the table, so it plays here too.
+    let mut player = if config.chart.is_some() {
+        Player::Chart
+    } else {
+        Player::Basic
+    };
+    let mut betting = Betting::Ramp(config.ramp.clone());
+    let mut single = false;
+
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("`{}` needs a value", arg))
+        };
+
+        match arg.as_str() {
+            "--hands" => {
+                simulation.hands = value()?
+                    .parse()
+                    .map_err(|_| "`--hands` takes a whole number".to_string())?
+            }
+            "--seed" => {
+                simulation.seed = value()?
+                    .parse()
+                    .map_err(|_| "`--seed` takes a whole number".to_string())?
+            }
+            "--threads" => {
+                simulation.threads = match value()?.parse() {
+                    Ok(threads) if threads > 0 => threads,
+                    _ => return Err("`--threads` takes a number above 0".to_string()),
+                }
+            }
+            "--player" => {
+                player = value()?.parse()?;
+                single = true;
+            }
+            "--flat" => {
+                betting = Betting::Flat;
+                single = true;
+            }
+            "--bankroll" => {
+                simulation.bankroll.amount = match value()?.parse::<Money>() {
+                    Ok(amount) if amount > Money::ZERO => amount,
+                    _ => return Err("`--bankroll` takes a positive amount of money".to_string()),
+                }
+            }
+            "--paths" => {
+                simulation.bankroll.paths = value()?
+                    .parse()
+                    .map_err(|_| "`--paths` takes a whole number".to_string())?
+            }
+            "--compare" => simulation
+                .contenders
+                .push(Contender::parse(value()?, &config.ramp)?),
+            _ => return Err(format!("unknown option `{}`", arg)),
+        }
+    }
+
+    if simulation.contenders.is_empty() {
+        simulation.contenders.push(Contender { player, betting });
+    } else if single {
+        return Err("give `--player` and `--flat` as part of `--compare` instead".to_string());
+    }
+
+    if config.chart.is_none()
+        && simulation
+            .contenders
+            .iter()
+            .any(|contender| contender.player == Player::Chart)
+    {
+        return Err(format!(
+            "no strategy chart to play, set [strategy] chart in {}",
+            CONFIG_PATH
+        ));
+    }
+
+    Ok(simulation)
+}
+
+fn print_simulation(simulation: &Simulation, config: &Config, results: &Results) {
+    let unit = config.ramp.unit.as_f64();
+
+    println!(
+        "{} hands on {} thread{}, seed {}",
+        simulation.hands,
+        simulation.threads,
+        if simulation.threads == 1 { "" } else { "s" },
+        simulation.seed
+    );
+
+    let bankroll = &simulation.bankroll;
+
+    for ((contender, stats), paths) in simulation
+        .contenders
+        .iter()
+        .zip(&results.stats)
+        .zip(&results.paths)
+    {
+        let (wins, pushes, losses) = stats.rates();
+        let risk = stats.risk(bankroll);
+
+        println!();
+        println!("{}", contender);
+        println!(
+            "House edge:          {:+.3}% ± {:.3}% (95% confidence)",
+            stats.house_edge() * 100.0,
+            stats.house_edge_margin() * 100.0
+        );
+        println!(
+            "Win per hand:        {:+.3} ({:+.4} units)",
+            stats.mean(),
+            stats.mean() / unit
+        );
+        println!(
+            "Standard deviation:  {:.3} ({:.4} units) per hand",
+            stats.standard_deviation(),
+            stats.standard_deviation() / unit
+        );
+        println!(
+            "Won / pushed / lost: {:.2}% / {:.2}% / {:.2}%",
+            wins * 100.0,
+            pushes * 100.0,
+            losses * 100.0
+        );
+        println!(
+            "Total bet {:.0}, average bet {:.2}, net {:+.0}",
+            stats.wagered,
+            stats.average_bet(),
+            stats.won
+        );
+        println!(
+            "Bankroll {} at {:.0} hands an hour, trips of {:.0} hours:",
+            bankroll.amount, bankroll.hands_per_hour, bankroll.hours
+        );
+        println!(
+            "  Hourly win:        {:+.2} ± {:.2} (one standard deviation)",
+            risk.hourly_win, risk.hourly_deviation
+        );
+        match risk.n0 {
+            Some(n0) => println!(
+                "  N0:                {:.0} hands ({:.1} hours)",
+                n0,
+                n0 / bankroll.hands_per_hour
+            ),
+            None => println!("  N0:                none, the house has the edge"),
+        }
+        println!(
+            "  Risk of ruin:      {:.2}% playing forever, {:.2}% in a trip",
+            risk.ruin * 100.0,
+            risk.trip_ruin * 100.0
+        );
+        if paths.paths > 0 {
+            print!(
+                "  Simulated trips:   {:.2}% of {} went broke",
+                paths.ruin() * 100.0,
+                paths.paths
+            );
+            match paths.hands_to_ruin() {
+                Some(hands) => println!(", after {:.0} hands on average", hands),
+                None => println!(),
+            }
+        }
+    }
+
+    if results.differences.is_empty() {
+        return;
+    }
+
+    println!();
+    println!("Against {}, on the same shoes:", simulation.contenders[0]);
+
+    for (contender, difference) in simulation.contenders[1..].iter().zip(&results.differences) {
+        println!(
+            "{}: {:+.4} ± {:.4} per hand ({:+.5} ± {:.5} units, standard error)",
+            contender,
+            difference.mean(),
+            difference.standard_error(),
+            difference.mean() / unit,
+            difference.standard_error() / unit
+        );
+    }
 }
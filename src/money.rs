@@ -0,0 +1,213 @@
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Mul, Sub, SubAssign},
+    str::FromStr,
+};
+
+const CURRENCY: &str = "$";
+const CENTS_PER_UNIT: i64 = 100;
+
+/// An exact amount of money in whole cents, so settling hand after hand never drifts the way
+/// adding up floats does.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Money(i64);
+
+impl Money {
+    pub const ZERO: Money = Money(0);
+
+    pub const fn from_cents(cents: i64) -> Self {
+        Self(cents)
+    }
+
+    /// A whole number of dollars.
+    pub const fn whole(units: i64) -> Self {
+        Self(units * CENTS_PER_UNIT)
+    }
+
+    /// The nearest cent to an amount worked out in floating point, like a bankroll from the
+    /// config or an expected value.
+    pub fn from_f64(amount: f64) -> Self {
+        Self((amount * CENTS_PER_UNIT as f64).round() as i64)
+    }
+
+    pub fn cents(self) -> i64 {
+        self.0
+    }
+
+    /// The amount in dollars, for statistics.
+    pub fn as_f64(self) -> f64 {
+        self.0 as f64 / CENTS_PER_UNIT as f64
+    }
+
+    pub fn abs(self) -> Self {
+        Self(self.0.abs())
+    }
+
+    /// The amount times a payout ratio like 3:2's 1.5, to the nearest cent.
+    pub fn scale(self, ratio: f32) -> Self {
+        Self((self.0 as f64 * ratio as f64).round() as i64)
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, other: Money) -> Money {
+        Money(self.0 + other.0)
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, other: Money) {
+        self.0 += other.0;
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, other: Money) -> Money {
+        Money(self.0 - other.0)
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, other: Money) {
+        self.0 -= other.0;
+    }
+}
+
+impl Mul<i64> for Money {
+    type Output = Money;
+
+    fn mul(self, times: i64) -> Money {
+        Money(self.0 * times)
+    }
+}
+
+/// Currency symbol and thousands separators, with cents only when there are any, e.g.
+/// `$1,250`, `-$12.50`, or `+$25` with the `+` flag.
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.0 < 0 {
+            "-"
+        } else if f.sign_plus() && self.0 > 0 {
+            "+"
+        } else {
+            ""
+        };
+
+        let units = (self.0.abs() / CENTS_PER_UNIT).to_string();
+        let cents = self.0.abs() % CENTS_PER_UNIT;
+
+        let mut grouped = String::new();
+
+        for (index, digit) in units.chars().enumerate() {
+            if index > 0 && (units.len() - index).is_multiple_of(3) {
+                grouped.push(',');
+            }
+            grouped.push(digit);
+        }
+
+        let text = if cents == 0 {
+            format!("{}{}{}", sign, CURRENCY, grouped)
+        } else {
+            format!("{}{}{}.{:02}", sign, CURRENCY, grouped, cents)
+        };
+
+        f.pad(&text)
+    }
+}
+
+/// Reads amounts written the way they are shown, like `125`, `$1,250`, `12.50` or `-$12.50`.
+impl FromStr for Money {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("`{}` isn't an amount of money", text.trim());
+
+        let amount = text.trim();
+        let (sign, amount) = match amount.strip_prefix('-') {
+            Some(amount) => (-1, amount),
+            None => (1, amount),
+        };
+        let amount = amount
+            .strip_prefix(CURRENCY)
+            .unwrap_or(amount)
+            .replace(',', "");
+        let (units, cents) = amount.split_once('.').unwrap_or((&amount, ""));
+
+        if units.is_empty()
+            || cents.len() > 2
+            || !units
+                .chars()
+                .chain(cents.chars())
+                .all(|c| c.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+
+        let units: i64 = units.parse().map_err(|_| invalid())?;
+        let cents = format!("{:0<2}", cents)
+            .parse::<i64>()
+            .map_err(|_| invalid())?;
+
+        units
+            .checked_mul(CENTS_PER_UNIT)
+            .and_then(|units| units.checked_add(cents))
+            .map(|cents| Money(sign * cents))
+            .ok_or_else(invalid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_currency_and_grouping() {
+        assert_eq!("$1,234.56".parse(), Ok(Money::from_cents(123_456)));
+        assert_eq!("125".parse(), Ok(Money::whole(125)));
+        assert_eq!(" 12.5 ".parse(), Ok(Money::from_cents(1250)));
+    }
+
+    #[test]
+    fn parses_negative_amounts() {
+        assert_eq!("-$12.50".parse(), Ok(Money::from_cents(-1250)));
+        assert_eq!("-3".parse(), Ok(Money::whole(-3)));
+        assert!("--3".parse::<Money>().is_err());
+        assert!("$-3".parse::<Money>().is_err());
+    }
+
+    #[test]
+    fn rejects_too_many_decimals() {
+        assert!("1.234".parse::<Money>().is_err());
+        assert!("$0.001".parse::<Money>().is_err());
+    }
+
+    #[test]
+    fn rejects_anything_else() {
+        for text in ["", "$", ".50", "1e3", "12.5.0", "€12", "ten"] {
+            assert!(text.parse::<Money>().is_err(), "`{}` parsed", text);
+        }
+    }
+
+    #[test]
+    fn displays_thousands_groups() {
+        assert_eq!(Money::whole(0).to_string(), "$0");
+        assert_eq!(Money::whole(999).to_string(), "$999");
+        assert_eq!(Money::whole(1000).to_string(), "$1,000");
+        assert_eq!(Money::from_cents(123_456_789).to_string(), "$1,234,567.89");
+        assert_eq!(Money::from_cents(-1250).to_string(), "-$12.50");
+        assert_eq!(format!("{:+}", Money::whole(25)), "+$25");
+    }
+
+    #[test]
+    fn reads_back_what_it_displays() {
+        for cents in [0, 5, 1250, -1250, 123_456_789] {
+            let money = Money::from_cents(cents);
+
+            assert_eq!(money.to_string().parse(), Ok(money));
+        }
+    }
+}
@@ -0,0 +1,350 @@
+use std::collections::HashMap;
+
+use crate::card::{Card, JokerRule, Rank, RankCounts, Suit, RANKS};
+use crate::game::{Game, Winner};
+use crate::rules::Rules;
+use crate::strategy::{
+    bust_value, card_value, stand_value, Action, DealerOutcomes, DEALER_BUST, DEALER_CHARLIE,
+};
+
+/// Card values drawn from: ace to ten-valued at 0 to 9, and jokers last.
+const CLASSES: usize = 11;
+const JOKER: usize = 10;
+
+type ClassCounts = [usize; CLASSES];
+
+/// Chance of winning, pushing and losing a hand.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Odds {
+    pub win: f64,
+    pub push: f64,
+    pub loss: f64,
+}
+
+impl Odds {
+    fn add(&mut self, winner: Winner, chance: f64) {
+        match winner {
+            Winner::Me => self.win += chance,
+            Winner::None => self.push += chance,
+            Winner::Dealer => self.loss += chance,
+        }
+    }
+}
+
+/// Exact chances for the hand in play, drawing without replacement from the cards the player
+/// has not seen.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ShoeOdds {
+    /// Dealer finishing on 17-21, busting or making a charlie, in that order.
+    pub dealer: DealerOutcomes,
+    pub stand: Odds,
+    /// Taking exactly one more card and then standing.
+    pub hit: Odds,
+}
+
+/// The draws a hand's outcome depends on: the cards left to draw from, and a full shoe to
+/// carry on with in case they run out.
+struct Draws<'a> {
+    rules: &'a Rules,
+    joker_rule: JokerRule,
+    counts: ClassCounts,
+    refill: ClassCounts,
+}
+
+impl ShoeOdds {
+    /// `unseen` is every card the player has not seen, including the dealer's hole card, and
+    /// `full` the counts of a complete shoe for when it is used up.
+    pub fn new(
+        my_hand: &[Card],
+        dealer_up: &[Card],
+        unseen: &RankCounts,
+        full: &RankCounts,
+        rules: &Rules,
+        joker_rule: JokerRule,
+    ) -> Self {
+        let mut draws = Draws {
+            rules,
+            joker_rule,
+            counts: classes(unseen),
+            refill: classes(full),
+        };
+
+        let mut dealer = [0.0; 7];
+        let mut stand = Odds::default();
+        let mut dealer_hand = cards_of(dealer_up);
+
+        draws.dealer_finals(&mut dealer_hand, 1.0, &mut |finished, chance| {
+            dealer[outcome_index(finished, rules, joker_rule)] += chance;
+            stand.add(Game::winner(my_hand, finished, rules, joker_rule), chance);
+        });
+
+        let mut hit = Odds::default();
+        let mut my_hand = cards_of(my_hand);
+
+        draws.draw(1.0, &mut |draws, card, chance| {
+            my_hand.push(card);
+
+            draws.dealer_finals(&mut dealer_hand, chance, &mut |finished, chance| {
+                hit.add(Game::winner(&my_hand, finished, rules, joker_rule), chance);
+            });
+
+            my_hand.pop();
+        });
+
+        Self { dealer, stand, hit }
+    }
+}
+
+/// Exact expected value per unit bet of every action the rules allow for the hand, drawing
+/// without replacement from the cards the player has not seen and playing on as well as possible
+/// after a hit. Each hand of a split is valued on its own from the shoe as it stands, leaving out
+/// the cards the other hand takes.
+pub fn action_values(
+    my_hand: &[Card],
+    dealer_up: &[Card],
+    unseen: &RankCounts,
+    full: &RankCounts,
+    rules: &Rules,
+    joker_rule: JokerRule,
+) -> Vec<(Action, f64)> {
+    let mut values = Values {
+        draws: Draws {
+            rules,
+            joker_rule,
+            counts: classes(unseen),
+            refill: classes(full),
+        },
+        dealer_up: cards_of(dealer_up),
+        dealer: HashMap::new(),
+        best: HashMap::new(),
+    };
+
+    let mut hand = cards_of(my_hand);
+
+    let mut actions = vec![
+        (Action::Hit, values.hit(&mut hand, false)),
+        (Action::Stand, values.stand(&hand)),
+    ];
+
+    if hand.len() == 2 {
+        if rules.double {
+            actions.push((Action::Double, 2.0 * values.hit(&mut hand, true)));
+        }
+
+        let pair = match (hand[0].rank(), hand[1].rank()) {
+            (Rank::Joker, _) | (_, Rank::Joker) => false,
+            (first, second) => card_value(first) == card_value(second),
+        };
+
+        if rules.split && pair {
+            let mut split_hand = cards_of(&hand[..1]);
+            let one_card = hand[0].is_ace();
+
+            actions.push((Action::Split, 2.0 * values.hit(&mut split_hand, one_card)));
+        }
+
+        if rules.surrender {
+            actions.push((Action::Surrender, -0.5));
+        }
+    }
+
+    actions
+}
+
+/// Expected values of player hands, remembering the dealer's chances and the best play for
+/// each set of cards left so hands reached in different orders are only worked out once.
+struct Values<'a> {
+    draws: Draws<'a>,
+    dealer_up: Vec<Card>,
+    dealer: HashMap<ClassCounts, DealerOutcomes>,
+    best: HashMap<(ClassCounts, usize), f64>,
+}
+
+impl Values<'_> {
+    fn dealer(&mut self) -> DealerOutcomes {
+        if let Some(outcomes) = self.dealer.get(&self.draws.counts) {
+            return *outcomes;
+        }
+
+        let (rules, joker_rule) = (self.draws.rules, self.draws.joker_rule);
+        let mut outcomes = [0.0; 7];
+
+        self.draws
+            .dealer_finals(&mut self.dealer_up, 1.0, &mut |finished, chance| {
+                outcomes[outcome_index(finished, rules, joker_rule)] += chance;
+            });
+
+        self.dealer.insert(self.draws.counts, outcomes);
+
+        outcomes
+    }
+
+    fn stand(&mut self, hand: &[Card]) -> f64 {
+        let dealer = self.dealer();
+        let value = Game::hand_value(hand, self.draws.joker_rule);
+
+        if value > 21 {
+            bust_value(self.draws.rules, &dealer)
+        } else {
+            let charlie = self.draws.rules.charlie == Some(hand.len());
+            stand_value(self.draws.rules, &dealer, value as usize, charlie)
+        }
+    }
+
+    /// Taking a card, then standing if `then_stand` or else playing on as well as possible.
+    fn hit(&mut self, hand: &mut Vec<Card>, then_stand: bool) -> f64 {
+        let mut value = 0.0;
+
+        self.draws_each(|values, card, chance| {
+            hand.push(card);
+            value += chance
+                * if then_stand {
+                    values.stand(hand)
+                } else {
+                    values.best(hand)
+                };
+            hand.pop();
+        });
+
+        value
+    }
+
+    /// The better of standing and hitting, once the hand is still open.
+    fn best(&mut self, hand: &mut Vec<Card>) -> f64 {
+        let value = Game::hand_value(hand, self.draws.joker_rule);
+
+        if value >= 21 || self.draws.rules.charlie == Some(hand.len()) {
+            return self.stand(hand);
+        }
+
+        let key = (self.draws.counts, hand.len());
+
+        if let Some(best) = self.best.get(&key) {
+            return *best;
+        }
+
+        let best = self.stand(hand).max(self.hit(hand, false));
+        self.best.insert(key, best);
+
+        best
+    }
+
+    fn draws_each(&mut self, mut visit: impl FnMut(&mut Self, Card, f64)) {
+        let total: usize = self.draws.counts.iter().sum();
+
+        if total == 0 {
+            let counts = std::mem::replace(&mut self.draws.counts, self.draws.refill);
+            self.draws_each(visit);
+            self.draws.counts = counts;
+            return;
+        }
+
+        for class in 0..CLASSES {
+            let count = self.draws.counts[class];
+
+            if count == 0 {
+                continue;
+            }
+
+            self.draws.counts[class] -= 1;
+            visit(self, card_of_class(class), count as f64 / total as f64);
+            self.draws.counts[class] += 1;
+        }
+    }
+}
+
+impl Draws<'_> {
+    /// Calls `visit` with every card that can come next and its chance, with the card taken
+    /// out of the counts for the duration of the call.
+    fn draw(&mut self, chance: f64, visit: &mut dyn FnMut(&mut Self, Card, f64)) {
+        let total: usize = self.counts.iter().sum();
+
+        if total == 0 {
+            let counts = std::mem::replace(&mut self.counts, self.refill);
+            self.draw(chance, visit);
+            self.counts = counts;
+            return;
+        }
+
+        for class in 0..CLASSES {
+            let count = self.counts[class];
+
+            if count == 0 {
+                continue;
+            }
+
+            self.counts[class] -= 1;
+            visit(
+                self,
+                card_of_class(class),
+                chance * count as f64 / total as f64,
+            );
+            self.counts[class] += 1;
+        }
+    }
+
+    /// Plays the dealer's hand out every way it can go, calling `visit` with each finished
+    /// hand and its chance.
+    fn dealer_finals(
+        &mut self,
+        hand: &mut Vec<Card>,
+        chance: f64,
+        visit: &mut dyn FnMut(&[Card], f64),
+    ) {
+        // Until the hole card is turned over the dealer always takes a second card.
+        if hand.len() >= 2 && !Game::dealer_should_draw(hand, self.rules, self.joker_rule) {
+            visit(hand, chance);
+            return;
+        }
+
+        self.draw(chance, &mut |draws, card, chance| {
+            hand.push(card);
+            draws.dealer_finals(hand, chance, visit);
+            hand.pop();
+        });
+    }
+}
+
+/// Where a finished dealer hand goes in `DealerOutcomes`.
+fn outcome_index(hand: &[Card], rules: &Rules, joker_rule: JokerRule) -> usize {
+    let value = Game::hand_value(hand, joker_rule);
+
+    if value > 21 {
+        DEALER_BUST
+    } else if rules.charlie == Some(hand.len()) {
+        DEALER_CHARLIE
+    } else {
+        value as usize - 17
+    }
+}
+
+fn classes(counts: &RankCounts) -> ClassCounts {
+    let mut classes = [0; CLASSES];
+
+    for (rank, count) in RANKS.iter().zip(counts) {
+        match card_value(*rank) {
+            Some(value) => classes[value - 1] += count,
+            None => classes[JOKER] += count,
+        }
+    }
+
+    classes
+}
+
+fn card_of_class(class: usize) -> Card {
+    let rank = match class {
+        JOKER => Rank::Joker,
+        9 => Rank::Ten,
+        class => RANKS[class],
+    };
+
+    Card::new(Suit::Spade, rank)
+}
+
+/// Copies of the cards, which are only ever looked at by rank.
+fn cards_of(cards: &[Card]) -> Vec<Card> {
+    cards
+        .iter()
+        .map(|card| Card::new(Suit::Spade, card.rank()))
+        .collect()
+}
@@ -0,0 +1,47 @@
+/// The table rules the game is dealt under and the strategy engine plays against. The game
+/// itself only deals hit and stand, doubling, splitting and surrendering are there for working
+/// out strategy for other tables.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rules {
+    /// Whether the dealer draws to a soft 17 instead of standing.
+    pub dealer_hits_soft_17: bool,
+    /// Number of cards that ends a hand without busting. A player charlie wins unless the dealer
+    /// also has one, a dealer charlie beats everything else. The dealer stops drawing at it too.
+    pub charlie: Option<usize>,
+    /// Winnings per unit bet on a won hand, on top of the returned stake.
+    pub win_pays: f32,
+    /// Whether the hand is a push when both the player and the dealer bust.
+    pub bust_push: bool,
+    /// Doubling down on the first two cards.
+    pub double: bool,
+    /// Splitting a pair into two hands, once, with split aces getting one card each.
+    pub split: bool,
+    /// Late surrender of the first two cards for half the bet.
+    pub surrender: bool,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            dealer_hits_soft_17: false,
+            charlie: Some(5),
+            win_pays: 0.5,
+            bust_push: true,
+            double: false,
+            split: false,
+            surrender: false,
+        }
+    }
+}
+
+impl Rules {
+    /// These rules as the game deals them, with no doubling, splitting or surrendering.
+    pub fn dealt(&self) -> Self {
+        Self {
+            double: false,
+            split: false,
+            surrender: false,
+            ..self.clone()
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::card::{Card, Composition, RankCounts, RANKS};
+use crate::fair::{commitment, Seed};
+use crate::shuffle::ShuffleModel;
+
+/// The commitment, seed, order the cards were gathered up in before the shuffle and dealt
+/// cards of one shoe, in the order they were dealt.
+pub struct ShoeRecord {
+    pub commitment: String,
+    pub seed: Seed,
+    pub gathered: Vec<String>,
+    pub dealt: Vec<String>,
+}
+
+pub struct Shoe {
+    rng: StdRng,
+    composition: Composition,
+    shuffle_model: ShuffleModel,
+    size: usize,
+    cards: Vec<Card>,
+    burnt: Vec<Card>,
+    discard: Vec<Card>,
+    records: Vec<ShoeRecord>,
+    /// Whether shoes are committed to and their dealt cards recorded for the fair log.
+    keep_records: bool,
+    shuffles: usize,
+}
+
+impl Shoe {
+    pub fn new(composition: Composition, shuffle_model: ShuffleModel) -> Self {
+        Self::with_rng(composition, shuffle_model, StdRng::from_entropy(), true)
+    }
+
+    /// A shoe whose shuffles all follow from one seed, for simulations. It keeps no records,
+    /// so playing millions of hands does not build up a log of every card dealt.
+    pub fn seeded(composition: Composition, shuffle_model: ShuffleModel, seed: u64) -> Self {
+        Self::with_rng(
+            composition,
+            shuffle_model,
+            StdRng::seed_from_u64(seed),
+            false,
+        )
+    }
+
+    fn with_rng(
+        composition: Composition,
+        shuffle_model: ShuffleModel,
+        rng: StdRng,
+        keep_records: bool,
+    ) -> Self {
+        let mut shoe = Self {
+            rng,
+            composition,
+            shuffle_model,
+            size: 0,
+            cards: Vec::new(),
+            burnt: Vec::new(),
+            discard: Vec::new(),
+            records: Vec::new(),
+            keep_records,
+            shuffles: 0,
+        };
+
+        shoe.shuffle();
+
+        shoe
+    }
+
+    /// Shuffles gathered up cards with a seed into the shoe it produces, with the top card last.
+    pub fn build(mut cards: Vec<Card>, shuffle_model: &ShuffleModel, seed: Seed) -> Vec<Card> {
+        shuffle_model.shuffle(&mut cards, &mut StdRng::from_seed(seed));
+
+        cards
+    }
+
+    /// Gathers up the discard tray, the burn cards and whatever is left on top of them and
+    /// shuffles that from a new seed, so a physical shuffle works on the order the last shoe
+    /// left behind. Seeded shoes sort the gathered cards first. The first shoe starts from fresh decks. Commits to the order before and
+    /// after the shuffle and burns the top card. Cards still on the table stay out of the new
+    /// shoe.
+    pub fn shuffle(&mut self) {
+        let seed: Seed = self.rng.gen();
+
+        let mut gathered = if self.shuffles == 0 {
+            Card::generate_custom_deck(&self.composition)
+        } else {
+            let mut gathered = std::mem::take(&mut self.discard);
+            gathered.append(&mut self.burnt);
+            gathered.append(&mut self.cards);
+            gathered
+        };
+
+        for card in &mut gathered {
+            card.show();
+        }
+
+        // Without a record there is no physical shuffle to carry on from, and simulations need
+        // shoe after shoe to follow from the seed alone, whatever order the hands were played
+        // in, so contenders in compare mode are dealt the same cards.
+        if !self.keep_records {
+            gathered.sort_by_cached_key(Card::code);
+        }
+
+        let order: Vec<String> = gathered.iter().map(Card::code).collect();
+
+        self.cards = Self::build(gathered, &self.shuffle_model, seed);
+        self.size = self.cards.len();
+        self.shuffles += 1;
+
+        if self.keep_records {
+            self.records.push(ShoeRecord {
+                commitment: commitment(&seed, &order, &self.cards),
+                seed,
+                gathered: order,
+                dealt: Vec::new(),
+            });
+        }
+
+        if let Some(mut card) = self.cards.pop() {
+            card.hide();
+            self.burnt.push(card);
+        }
+    }
+
+    pub fn deal(&mut self) -> Card {
+        if self.cards.is_empty() {
+            self.shuffle();
+        }
+
+        let card = self
+            .cards
+            .pop()
+            .expect("a freshly shuffled shoe always has cards");
+
+        if let Some(record) = self.records.last_mut() {
+            record.dealt.push(card.code());
+        }
+
+        card
+    }
+
+    pub fn discard(&mut self, cards: &mut Vec<Card>) {
+        for card in cards.iter_mut() {
+            card.show();
+        }
+
+        self.discard.append(cards);
+    }
+
+    /// Number of undealt cards left in the shoe.
+    pub fn cards_remaining(&self) -> usize {
+        self.cards.len()
+    }
+
+    /// Number of cards in the shoe when it is complete.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Number of times the shoe has been shuffled, the first fill included.
+    pub fn shuffles(&self) -> usize {
+        self.shuffles
+    }
+
+    pub fn discard_len(&self) -> usize {
+        self.discard.len()
+    }
+
+    /// Commitment to the order of the shoe currently in play.
+    pub fn commitment(&self) -> &str {
+        self.records
+            .last()
+            .map_or("", |record| record.commitment.as_str())
+    }
+
+    /// Every shoe shuffled this session. All but the last have been exhausted and can have
+    /// their seeds revealed.
+    pub fn records(&self) -> &[ShoeRecord] {
+        &self.records
+    }
+
+    /// Counts by rank of the cards in the discard tray.
+    pub fn seen_counts(&self) -> RankCounts {
+        Self::count(&self.discard)
+    }
+
+    /// Counts by rank of the undealt and burnt cards, which the player has not seen.
+    pub fn unseen_counts(&self) -> RankCounts {
+        let mut counts = Self::count(&self.cards);
+
+        for (count, burnt) in counts.iter_mut().zip(Self::count(&self.burnt)) {
+            *count += burnt;
+        }
+
+        counts
+    }
+
+    /// Counts by rank of a complete shoe.
+    pub fn full_counts(&self) -> RankCounts {
+        Self::count(&Card::generate_custom_deck(&self.composition))
+    }
+
+    fn count(cards: &[Card]) -> RankCounts {
+        let mut counts = [0; RANKS.len()];
+
+        for card in cards {
+            counts[card.rank().index()] += 1;
+        }
+
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deals out a seeded shoe, discarding the cards in dealt or reversed order, and returns
+    /// the codes of the next shoe.
+    fn next_shoe(reversed: bool) -> Vec<String> {
+        let mut shoe = Shoe::seeded(Composition::standard(1), ShuffleModel::default(), 7);
+        let mut dealt: Vec<Card> = (0..shoe.cards_remaining()).map(|_| shoe.deal()).collect();
+
+        if reversed {
+            dealt.reverse();
+        }
+
+        shoe.discard(&mut dealt);
+        shoe.shuffle();
+
+        (0..shoe.cards_remaining())
+            .map(|_| shoe.deal().code())
+            .collect()
+    }
+
+    #[test]
+    fn seeded_shoes_ignore_the_discard_order() {
+        assert_eq!(next_shoe(false), next_shoe(true));
+    }
+}
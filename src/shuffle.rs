@@ -0,0 +1,149 @@
+use std::{fmt, str::FromStr};
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::card::Card;
+
+const STRIP_PACKETS: usize = 6;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ShuffleStep {
+    Riffle,
+    Strip,
+    Cut,
+}
+
+/// How the shoe is shuffled. The end of the card vector is the top of the stack.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum ShuffleModel {
+    /// Every order equally likely.
+    #[default]
+    Uniform,
+    /// A sequence of hand shuffles, which leaves clumps from the previous order behind.
+    Physical(Vec<ShuffleStep>),
+}
+
+impl ShuffleModel {
+    pub fn shuffle<R: Rng>(&self, cards: &mut Vec<Card>, rng: &mut R) {
+        match self {
+            ShuffleModel::Uniform => cards.shuffle(rng),
+            ShuffleModel::Physical(steps) => {
+                for step in steps {
+                    match step {
+                        ShuffleStep::Riffle => riffle(cards, rng),
+                        ShuffleStep::Strip => strip(cards, rng),
+                        ShuffleStep::Cut => cut(cards, rng),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Writes the model the way `from_str` reads it, steps comma separated without spaces.
+impl fmt::Display for ShuffleModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ShuffleModel::Uniform => write!(f, "uniform"),
+            ShuffleModel::Physical(steps) => {
+                let names: Vec<&str> = steps
+                    .iter()
+                    .map(|step| match step {
+                        ShuffleStep::Riffle => "riffle",
+                        ShuffleStep::Strip => "strip",
+                        ShuffleStep::Cut => "cut",
+                    })
+                    .collect();
+
+                write!(f, "{}", names.join(","))
+            }
+        }
+    }
+}
+
+impl FromStr for ShuffleModel {
+    type Err = String;
+
+    /// Parses `uniform`, or a comma separated list of `riffle`, `strip` and `cut` where each
+    /// step may be repeated with a count, e.g. `riffle*3, strip, riffle, cut`.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        if text.trim() == "uniform" {
+            return Ok(ShuffleModel::Uniform);
+        }
+
+        let mut steps = Vec::new();
+
+        for item in text.split(',') {
+            let (name, repeat) = match item.split_once('*') {
+                Some((name, repeat)) => (
+                    name.trim(),
+                    repeat
+                        .trim()
+                        .parse::<usize>()
+                        .map_err(|_| format!("invalid repeat count in `{}`", item.trim()))?,
+                ),
+                None => (item.trim(), 1),
+            };
+
+            let step = match name {
+                "riffle" => ShuffleStep::Riffle,
+                "strip" => ShuffleStep::Strip,
+                "cut" => ShuffleStep::Cut,
+                _ => return Err(format!("unknown shuffle step `{}`", name)),
+            };
+
+            steps.extend(std::iter::repeat_n(step, repeat));
+        }
+
+        if steps.is_empty() {
+            return Err("shuffle needs at least one step".to_string());
+        }
+
+        Ok(ShuffleModel::Physical(steps))
+    }
+}
+
+/// Number of heads in `n` fair coin flips, which is where a dealer's cut lands.
+fn binomial_cut<R: Rng>(n: usize, rng: &mut R) -> usize {
+    (0..n).filter(|_| rng.gen_bool(0.5)).count()
+}
+
+/// Gilbert–Shannon–Reeds riffle: cut the stack binomially, then drop cards from either half
+/// with probability proportional to the size of that half.
+fn riffle<R: Rng>(cards: &mut Vec<Card>, rng: &mut R) {
+    let mut right = cards.split_off(binomial_cut(cards.len(), rng));
+    let mut left = std::mem::take(cards);
+
+    left.reverse();
+    right.reverse();
+
+    while !left.is_empty() || !right.is_empty() {
+        let from_left = rng.gen_range(0..left.len() + right.len()) < left.len();
+
+        let card = if from_left { left.pop() } else { right.pop() };
+        cards.extend(card);
+    }
+}
+
+/// Pull packets off the top onto a new pile, reversing the order of the packets but not the
+/// cards inside them.
+fn strip<R: Rng>(cards: &mut Vec<Card>, rng: &mut R) {
+    let average = (cards.len() / STRIP_PACKETS).max(1);
+    let mut pile = Vec::with_capacity(cards.len());
+
+    while !cards.is_empty() {
+        let size = rng
+            .gen_range(average / 2..=average + average / 2)
+            .clamp(1, cards.len());
+
+        let packet = cards.split_off(cards.len() - size);
+        pile.extend(packet);
+    }
+
+    *cards = pile;
+}
+
+fn cut<R: Rng>(cards: &mut [Card], rng: &mut R) {
+    let position = binomial_cut(cards.len(), rng);
+    cards.rotate_left(position);
+}
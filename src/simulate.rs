@@ -0,0 +1,528 @@
+use std::{
+    fmt,
+    str::FromStr,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::bankroll::{Bankroll, Paths, Risk};
+use crate::betting::{parse_steps, BetRamp};
+use crate::chart::ChartStrategy;
+use crate::config::Config;
+use crate::deviation::IndexStrategy;
+use crate::game::{Game, State, Winner};
+use crate::money::Money;
+use crate::strategy::{BasicStrategy, Strategy};
+
+/// z-score of a two sided 95% confidence interval.
+const Z_95: f64 = 1.96;
+
+/// Hands dealt from each shoe seeded off the master seed. Fixed, so a seed gives the same
+/// results however many threads play it.
+const BATCH_HANDS: u64 = 100_000;
+/// Bankrolls played from each shoe seeded off the master seed.
+const BATCH_PATHS: u64 = 10;
+
+/// Kept apart in the master seed so the hands and the bankrolls are dealt different shoes.
+const HANDS_STREAM: u64 = 0;
+const PATHS_STREAM: u64 = 1;
+
+/// How the simulated player picks plays.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Player {
+    /// Basic strategy from the strategy engine.
+    Basic,
+    /// Basic strategy with the configured deviations at their indexes.
+    Deviations,
+    /// The configured strategy chart.
+    Chart,
+}
+
+impl Player {
+    /// The player's strategy, the chart falling back to the strategy engine if there is none
+    /// configured. It picks from hit and stand only, the plays the game deals.
+    pub fn strategy(&self, config: &Config) -> Box<dyn Strategy> {
+        let rules = config.rules.dealt();
+        let basic = BasicStrategy::new(&rules, &config.composition);
+
+        match (self, &config.chart) {
+            (Player::Deviations, _) => {
+                Box::new(IndexStrategy::new(basic, config.deviations.clone()))
+            }
+            (Player::Chart, Some(chart)) => Box::new(ChartStrategy::new(
+                chart.clone(),
+                &rules,
+                config.composition.joker_rule,
+            )),
+            _ => Box::new(basic),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Player::Basic => "basic strategy",
+            Player::Deviations => "basic strategy with deviations",
+            Player::Chart => "strategy chart",
+        }
+    }
+}
+
+impl FromStr for Player {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "basic" => Ok(Player::Basic),
+            "deviations" => Ok(Player::Deviations),
+            "chart" => Ok(Player::Chart),
+            _ => Err(format!(
+                "unknown player `{}`, expected basic, deviations or chart",
+                text
+            )),
+        }
+    }
+}
+
+/// How a contender bets.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Betting {
+    /// The table minimum every hand.
+    Flat,
+    Ramp(BetRamp),
+}
+
+/// A way of playing and betting to simulate.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Contender {
+    pub player: Player,
+    pub betting: Betting,
+}
+
+impl Contender {
+    /// Parses `<player>`, `<player>@flat` or `<player>@<ramp steps>` such as
+    /// `deviations@0:1, 2:4, 4:8`. Leaving out the betting uses `ramp`, and steps given take
+    /// its unit.
+    pub fn parse(text: &str, ramp: &BetRamp) -> Result<Self, String> {
+        let (player, betting) = match text.split_once('@') {
+            Some((player, betting)) => (player, Some(betting.trim())),
+            None => (text, None),
+        };
+
+        let betting = match betting {
+            None => Betting::Ramp(ramp.clone()),
+            Some(betting) if betting.eq_ignore_ascii_case("flat") => Betting::Flat,
+            Some(steps) => Betting::Ramp(BetRamp {
+                steps: parse_steps(steps)?,
+                ..ramp.clone()
+            }),
+        };
+
+        Ok(Self {
+            player: player.parse()?,
+            betting,
+        })
+    }
+}
+
+impl fmt::Display for Contender {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}, ", self.player.name())?;
+
+        match &self.betting {
+            Betting::Flat => write!(f, "flat bets"),
+            Betting::Ramp(ramp) => {
+                let steps: Vec<String> = ramp
+                    .steps
+                    .iter()
+                    .map(|(count, units)| format!("{}:{}", count, units))
+                    .collect();
+
+                write!(f, "bet ramp {}", steps.join(", "))
+            }
+        }
+    }
+}
+
+/// A run of hands to simulate.
+#[derive(Clone, Debug)]
+pub struct Simulation {
+    pub hands: u64,
+    pub seed: u64,
+    /// Played side by side on the same shoes, so their results can be compared with little of
+    /// the luck of the cards left in the difference.
+    pub contenders: Vec<Contender>,
+    pub threads: usize,
+    pub bankroll: Bankroll,
+}
+
+impl Simulation {
+    /// Plays every hand through the game engine with no one at the keys, then plays out
+    /// bankrolls trip by trip for the risk of ruin.
+    pub fn run(&self, config: &Config) -> Results {
+        self.run_with_progress(config, &AtomicUsize::new(0))
+    }
+
+    /// How many batches of hands and bankrolls `run` plays, to show progress against.
+    pub fn batches(&self) -> usize {
+        (self.hands.div_ceil(BATCH_HANDS) + (self.bankroll.paths as u64).div_ceil(BATCH_PATHS))
+            as usize
+    }
+
+    /// `run`, counting each batch in `progress` as it finishes so another thread can show how
+    /// far along it is.
+    pub fn run_with_progress(&self, config: &Config, progress: &AtomicUsize) -> Results {
+        let batches = self.in_batches(
+            config,
+            HANDS_STREAM,
+            self.hands.div_ceil(BATCH_HANDS),
+            progress,
+            |strategies, batch, seed| {
+                let hands = BATCH_HANDS.min(self.hands - batch * BATCH_HANDS);
+                self.play(config, strategies, seed, hands)
+            },
+        );
+
+        let mut results = Results::new(self.contenders.len());
+
+        for batch in &batches {
+            results.add(batch);
+        }
+
+        let paths = self.bankroll.paths as u64;
+        let batches = self.in_batches(
+            config,
+            PATHS_STREAM,
+            paths.div_ceil(BATCH_PATHS),
+            progress,
+            |strategies, batch, seed| {
+                let paths = BATCH_PATHS.min(paths - batch * BATCH_PATHS);
+                self.play_paths(config, strategies, seed, paths)
+            },
+        );
+
+        for batch in &batches {
+            for (paths, other) in results.paths.iter_mut().zip(batch) {
+                paths.add(other);
+            }
+        }
+
+        results
+    }
+
+    /// Runs `work` on every batch, shared out between the threads. Each batch has its own seed
+    /// from the master seed and the results come back in batch order, so the threads only change
+    /// how long it takes.
+    fn in_batches<T: Send>(
+        &self,
+        config: &Config,
+        stream: u64,
+        batches: u64,
+        progress: &AtomicUsize,
+        work: impl Fn(&[Box<dyn Strategy>], u64, u64) -> T + Sync,
+    ) -> Vec<T> {
+        let mut master_seed = [0; 32];
+        master_seed[..8].copy_from_slice(&self.seed.to_le_bytes());
+        master_seed[8..16].copy_from_slice(&stream.to_le_bytes());
+
+        let mut master = StdRng::from_seed(master_seed);
+        let seeds: Vec<u64> = (0..batches).map(|_| master.gen()).collect();
+        let next_batch = AtomicUsize::new(0);
+
+        let mut done: Vec<(usize, T)> = thread::scope(|scope| {
+            let workers: Vec<_> = (0..self.threads.max(1))
+                .map(|_| {
+                    scope.spawn(|| {
+                        let strategies: Vec<Box<dyn Strategy>> = self
+                            .contenders
+                            .iter()
+                            .map(|contender| contender.player.strategy(config))
+                            .collect();
+                        let mut done = Vec::new();
+
+                        loop {
+                            let batch = next_batch.fetch_add(1, Ordering::Relaxed);
+                            let Some(seed) = seeds.get(batch) else {
+                                break;
+                            };
+
+                            done.push((batch, work(&strategies, batch as u64, *seed)));
+                            progress.fetch_add(1, Ordering::Relaxed);
+                        }
+
+                        done
+                    })
+                })
+                .collect();
+
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("a simulation thread panicked"))
+                .collect()
+        });
+
+        done.sort_by_key(|(batch, _)| *batch);
+
+        done.into_iter().map(|(_, result)| result).collect()
+    }
+
+    /// A game for every contender, all dealt from the same seed.
+    fn games(&self, config: &Config, seed: u64) -> Vec<Game> {
+        self.contenders
+            .iter()
+            .map(|contender| {
+                let mut game = Game::with_seed(config, seed);
+
+                if let Betting::Ramp(ramp) = &contender.betting {
+                    game.set_ramp(ramp.clone());
+                }
+
+                game
+            })
+            .collect()
+    }
+
+    /// Sets the contender's bet for the next hand, returning it.
+    fn place_bet(contender: &Contender, game: &mut Game) -> Money {
+        if let Betting::Ramp(_) = contender.betting {
+            game.set_bet(game.advised_bet());
+        }
+
+        game.bet()
+    }
+
+    /// Plays a batch of hands for every contender.
+    fn play(
+        &self,
+        config: &Config,
+        strategies: &[Box<dyn Strategy>],
+        seed: u64,
+        hands: u64,
+    ) -> Results {
+        let mut games = self.games(config, seed);
+        let mut results = Results::new(self.contenders.len());
+        let mut won = vec![Money::ZERO; self.contenders.len()];
+
+        for _ in 0..hands {
+            for (index, contender) in self.contenders.iter().enumerate() {
+                let game = &mut games[index];
+                let bet = Self::place_bet(contender, game);
+                let result = game.play_round(strategies[index].as_ref());
+                won[index] = result;
+
+                if let State::Result(winner) = game.state() {
+                    results.stats[index].record(bet, result, winner);
+                }
+            }
+
+            for (difference, other) in results.differences.iter_mut().zip(&won[1..]) {
+                difference.record((*other - won[0]).as_f64());
+            }
+        }
+
+        results
+    }
+
+    /// Plays a batch of bankrolls through a trip for every contender, stopping each one once it
+    /// can no longer cover the next bet.
+    fn play_paths(
+        &self,
+        config: &Config,
+        strategies: &[Box<dyn Strategy>],
+        seed: u64,
+        paths: u64,
+    ) -> Vec<Paths> {
+        let mut games = self.games(config, seed);
+        let mut played = vec![Paths::default(); self.contenders.len()];
+
+        for (index, contender) in self.contenders.iter().enumerate() {
+            let game = &mut games[index];
+
+            for _ in 0..paths {
+                let mut balance = self.bankroll.amount;
+                let mut ruined_after = None;
+
+                for hand in 0..self.bankroll.horizon() {
+                    if Self::place_bet(contender, game) > balance {
+                        ruined_after = Some(hand);
+                        break;
+                    }
+
+                    balance += game.play_round(strategies[index].as_ref());
+                }
+
+                played[index].record(ruined_after);
+            }
+        }
+
+        played
+    }
+}
+
+/// What each contender did, and how each after the first did against the first.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Results {
+    pub stats: Vec<Stats>,
+    pub differences: Vec<Difference>,
+    /// Bankrolls played out by each contender.
+    pub paths: Vec<Paths>,
+}
+
+impl Results {
+    fn new(contenders: usize) -> Self {
+        Self {
+            stats: vec![Stats::default(); contenders],
+            differences: vec![Difference::default(); contenders.saturating_sub(1)],
+            paths: vec![Paths::default(); contenders],
+        }
+    }
+
+    fn add(&mut self, other: &Results) {
+        for (stats, other) in self.stats.iter_mut().zip(&other.stats) {
+            stats.add(other);
+        }
+
+        for (difference, other) in self.differences.iter_mut().zip(&other.differences) {
+            difference.add(other);
+        }
+
+        for (paths, other) in self.paths.iter_mut().zip(&other.paths) {
+            paths.add(other);
+        }
+    }
+}
+
+/// Money won on each hand by one contender less what the first won on the same hand.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Difference {
+    pub hands: u64,
+    pub total: f64,
+    total_squared: f64,
+}
+
+impl Difference {
+    pub fn record(&mut self, difference: f64) {
+        self.hands += 1;
+        self.total += difference;
+        self.total_squared += difference * difference;
+    }
+
+    pub fn add(&mut self, other: &Difference) {
+        self.hands += other.hands;
+        self.total += other.total;
+        self.total_squared += other.total_squared;
+    }
+
+    /// Average difference per hand.
+    pub fn mean(&self) -> f64 {
+        self.total / self.hands.max(1) as f64
+    }
+
+    /// Standard error of the average difference per hand.
+    pub fn standard_error(&self) -> f64 {
+        if self.hands < 2 {
+            return 0.0;
+        }
+
+        let hands = self.hands as f64;
+        let variance = (self.total_squared - self.total * self.total / hands) / (hands - 1.0);
+
+        (variance.max(0.0) / hands).sqrt()
+    }
+}
+
+/// Results of simulated hands, kept as sums so runs can be added together.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Stats {
+    pub hands: u64,
+    pub wins: u64,
+    pub pushes: u64,
+    pub losses: u64,
+    /// Money bet over every hand.
+    pub wagered: f64,
+    /// Money won over every hand, negative for a loss.
+    pub won: f64,
+    won_squared: f64,
+}
+
+impl Stats {
+    pub fn record(&mut self, bet: Money, result: Money, winner: Winner) {
+        self.hands += 1;
+        self.wagered += bet.as_f64();
+        self.won += result.as_f64();
+        self.won_squared += result.as_f64().powi(2);
+
+        match winner {
+            Winner::Me => self.wins += 1,
+            Winner::None => self.pushes += 1,
+            Winner::Dealer => self.losses += 1,
+        }
+    }
+
+    /// Adds in the hands of another run.
+    pub fn add(&mut self, other: &Stats) {
+        self.hands += other.hands;
+        self.wins += other.wins;
+        self.pushes += other.pushes;
+        self.losses += other.losses;
+        self.wagered += other.wagered;
+        self.won += other.won;
+        self.won_squared += other.won_squared;
+    }
+
+    /// Average money won per hand.
+    pub fn mean(&self) -> f64 {
+        self.won / self.hands.max(1) as f64
+    }
+
+    /// Standard deviation of the money won on a hand.
+    pub fn standard_deviation(&self) -> f64 {
+        if self.hands < 2 {
+            return 0.0;
+        }
+
+        let hands = self.hands as f64;
+        let variance = (self.won_squared - self.won * self.won / hands) / (hands - 1.0);
+
+        variance.max(0.0).sqrt()
+    }
+
+    pub fn average_bet(&self) -> f64 {
+        self.wagered / self.hands.max(1) as f64
+    }
+
+    /// Share of the money bet the house keeps, negative when the player has the edge.
+    pub fn house_edge(&self) -> f64 {
+        if self.wagered == 0.0 {
+            return 0.0;
+        }
+
+        -self.won / self.wagered
+    }
+
+    /// Half the width of the 95% confidence interval around the house edge.
+    pub fn house_edge_margin(&self) -> f64 {
+        if self.wagered == 0.0 {
+            return 0.0;
+        }
+
+        Z_95 * self.standard_deviation() / (self.hands as f64).sqrt() / self.average_bet()
+    }
+
+    /// Risk for a bankroll worked out from the win and standard deviation per hand.
+    pub fn risk(&self, bankroll: &Bankroll) -> Risk {
+        Risk::new(self.mean(), self.standard_deviation(), bankroll)
+    }
+
+    /// Shares of hands won, pushed and lost.
+    pub fn rates(&self) -> (f64, f64, f64) {
+        let hands = self.hands.max(1) as f64;
+
+        (
+            self.wins as f64 / hands,
+            self.pushes as f64 / hands,
+            self.losses as f64 / hands,
+        )
+    }
+}
@@ -0,0 +1,558 @@
+use std::{fmt, str::FromStr};
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::card::{Card, Composition, JokerRule, Rank, Suit, RANKS};
+use crate::game::Game;
+use crate::rules::Rules;
+
+/// Card values from ace (1) to the ten-valued ranks (10).
+const VALUES: usize = 10;
+
+pub const DEALER_BUST: usize = 5;
+pub const DEALER_CHARLIE: usize = 6;
+
+/// Chance of the dealer finishing on 17-21, busting or making a charlie, in that order.
+pub type DealerOutcomes = [f64; 7];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    Hit,
+    Stand,
+    Double,
+    Split,
+    Surrender,
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Action::Hit => "Hit",
+            Action::Stand => "Stand",
+            Action::Double => "Double",
+            Action::Split => "Split",
+            Action::Surrender => "Surrender",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Action {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "hit" => Ok(Action::Hit),
+            "stand" => Ok(Action::Stand),
+            "double" => Ok(Action::Double),
+            "split" => Ok(Action::Split),
+            "surrender" => Ok(Action::Surrender),
+            _ => Err(format!(
+                "unknown action `{}`, expected hit, stand, double, split or surrender",
+                text
+            )),
+        }
+    }
+}
+
+/// The kind of hand a decision is made on, by total or by the value of the paired cards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Situation {
+    Hard(usize),
+    Soft(usize),
+    Pair(usize),
+}
+
+impl Situation {
+    /// Two cards making the situation, never a pair unless one is asked for or there is no
+    /// other way to make the total.
+    pub fn example_hand<R: Rng>(&self, rng: &mut R) -> Vec<Card> {
+        let (first, second) = match *self {
+            Situation::Pair(value) => (value, value),
+            Situation::Soft(total) => (1, total - 11),
+            Situation::Hard(total) => {
+                let splits: Vec<usize> = (2..=10)
+                    .filter(|first| (2..=10).contains(&(total as i32 - *first as i32)))
+                    .collect();
+                let unpaired: Vec<usize> = splits
+                    .iter()
+                    .copied()
+                    .filter(|first| total - first != *first)
+                    .collect();
+
+                let first = *unpaired.choose(rng).or(splits.first()).unwrap();
+                (first, total - first)
+            }
+        };
+
+        vec![card_of_value(first, rng), card_of_value(second, rng)]
+    }
+
+    pub fn of(hand: &[Card], joker_rule: JokerRule) -> Self {
+        if let [first, second] = hand {
+            if let (Some(a), Some(b)) = (card_value(first.rank()), card_value(second.rank())) {
+                if a == b {
+                    return Situation::Pair(a);
+                }
+            }
+        }
+
+        let total = Game::hand_value(hand, joker_rule) as usize;
+
+        if Game::is_soft(hand, joker_rule) {
+            Situation::Soft(total)
+        } else {
+            Situation::Hard(total)
+        }
+    }
+}
+
+impl fmt::Display for Situation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Situation::Hard(total) => write!(f, "Hard {}", total),
+            Situation::Soft(total) => write!(f, "Soft {}", total),
+            Situation::Pair(value) => {
+                let card = value_symbol(*value);
+                write!(f, "Pair {},{}", card, card)
+            }
+        }
+    }
+}
+
+/// Reads the same form `Display` writes, `Hard 16`, `Soft 18` or `Pair 8,8`, with the second
+/// card of a pair optional. Totals of 21 are left out as there is nothing to decide.
+impl FromStr for Situation {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "expected a hand like `hard 16`, `soft 18` or `pair 8`, got `{}`",
+                text
+            )
+        };
+
+        let (kind, value) = text.trim().split_once(' ').ok_or_else(invalid)?;
+        let value = value.trim();
+
+        match kind.to_ascii_lowercase().as_str() {
+            "hard" => match value.parse() {
+                Ok(total @ 4..=20) => Ok(Situation::Hard(total)),
+                _ => Err(invalid()),
+            },
+            "soft" => match value.parse() {
+                Ok(total @ 13..=20) => Ok(Situation::Soft(total)),
+                _ => Err(invalid()),
+            },
+            "pair" => {
+                let card = value.split(',').next().unwrap_or_default().trim();
+
+                match card.parse::<Rank>().ok().and_then(card_value) {
+                    Some(value) => Ok(Situation::Pair(value)),
+                    None => Err(invalid()),
+                }
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// The recommended action with a one line explanation.
+pub struct Hint {
+    pub action: Action,
+    pub reason: String,
+}
+
+/// Short name of a card value, aces as `A`.
+pub fn value_symbol(value: usize) -> String {
+    match value {
+        1 => "A".to_string(),
+        value => value.to_string(),
+    }
+}
+
+/// A card of the value, aces as 1, in a random suit and a random ten-valued rank for 10.
+pub fn card_of_value<R: Rng>(value: usize, rng: &mut R) -> Card {
+    let suit = [Suit::Diamond, Suit::Heart, Suit::Club, Suit::Spade]
+        .choose(rng)
+        .unwrap()
+        .clone();
+
+    let rank = match value {
+        10 => *[Rank::Ten, Rank::Jack, Rank::Queen, Rank::King]
+            .choose(rng)
+            .unwrap(),
+        value => RANKS[value - 1],
+    };
+
+    Card::new(suit, rank)
+}
+
+/// Blackjack value of a rank with aces as 1, or `None` for a joker.
+pub fn card_value(rank: Rank) -> Option<usize> {
+    match rank {
+        Rank::Ace => Some(1),
+        Rank::Two => Some(2),
+        Rank::Three => Some(3),
+        Rank::Four => Some(4),
+        Rank::Five => Some(5),
+        Rank::Six => Some(6),
+        Rank::Seven => Some(7),
+        Rank::Eight => Some(8),
+        Rank::Nine => Some(9),
+        Rank::Ten | Rank::Jack | Rank::Queen | Rank::King => Some(10),
+        Rank::Joker => None,
+    }
+}
+
+/// A way of playing hands, for the simulator to play with.
+pub trait Strategy {
+    /// The play for a hand against the dealer's up-card at a true count.
+    fn play(&self, hand: &[Card], up_card: &Card, true_count: f32) -> Action;
+}
+
+/// Basic strategy worked out from the rules rather than a fixed chart. Expected values are
+/// exact for an infinite shoe with the same proportions of each card value as the configured
+/// composition. Jokers are left out of the proportions, and a hand holding one is played as a
+/// hard total of its best value.
+pub struct BasicStrategy {
+    rules: Rules,
+    joker_rule: JokerRule,
+    probabilities: [f64; VALUES],
+    dealer: [DealerOutcomes; VALUES],
+    /// Expected value of playing on optimally, by up-card, hard total, whether there is an ace
+    /// and number of cards.
+    best: Vec<f64>,
+    card_slots: usize,
+}
+
+impl BasicStrategy {
+    pub fn new(rules: &Rules, composition: &Composition) -> Self {
+        let mut counts = [0.0; VALUES];
+
+        for card in Card::generate_custom_deck(composition) {
+            if let Some(value) = card_value(card.rank()) {
+                counts[value - 1] += 1.0;
+            }
+        }
+
+        let total: f64 = counts.iter().sum();
+        let probabilities = counts.map(|count| count / total);
+
+        let mut strategy = Self {
+            rules: rules.clone(),
+            joker_rule: composition.joker_rule,
+            probabilities,
+            dealer: [[0.0; 7]; VALUES],
+            best: Vec::new(),
+            card_slots: rules.charlie.map_or(1, |charlie| charlie + 1),
+        };
+
+        for up in 1..=VALUES {
+            strategy.dealer[up - 1] = strategy.dealer_from(up, up == 1, 1);
+        }
+
+        strategy.best = vec![0.0; VALUES * 22 * 2 * strategy.card_slots];
+
+        // Hitting only ever raises the hard total, so fill the table from the top down.
+        for up in 1..=VALUES {
+            for hard in (2..=21).rev() {
+                for ace in [false, true] {
+                    for cards in 1..strategy.card_slots.max(2) {
+                        let value = strategy.play_on(up, hard, ace, cards);
+                        let index = strategy.index(up, hard, ace, cards);
+                        strategy.best[index] = value;
+                    }
+                }
+            }
+        }
+
+        strategy
+    }
+
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    pub fn joker_rule(&self) -> JokerRule {
+        self.joker_rule
+    }
+
+    /// Chance of each dealer result for an up-card value, aces as 1.
+    pub fn dealer_outcomes(&self, up: usize) -> &DealerOutcomes {
+        &self.dealer[up - 1]
+    }
+
+    /// Expected value per unit bet of every legal action for the hand against the dealer's
+    /// up-card.
+    pub fn evaluate(&self, hand: &[Card], up_card: &Card) -> Vec<(Action, f64)> {
+        let up = card_value(up_card.rank()).unwrap_or(1);
+        let (hard, ace) = self.hand_state(hand);
+        let cards = hand.len();
+        let dealer = &self.dealer[up - 1];
+
+        let mut actions = vec![
+            (Action::Hit, self.hit(up, hard, ace, cards)),
+            (
+                Action::Stand,
+                self.stand(dealer, soft_total(hard, ace), false),
+            ),
+        ];
+
+        if cards == 2 {
+            if self.rules.double {
+                actions.push((Action::Double, self.double(up, hard, ace, cards)));
+            }
+
+            let pair = match (hand[0].rank(), hand[1].rank()) {
+                (Rank::Joker, _) | (_, Rank::Joker) => None,
+                (first, second) if card_value(first) == card_value(second) => card_value(first),
+                _ => None,
+            };
+
+            if let (true, Some(value)) = (self.rules.split, pair) {
+                actions.push((Action::Split, self.split(up, value)));
+            }
+
+            if self.rules.surrender {
+                actions.push((Action::Surrender, -0.5));
+            }
+        }
+
+        actions
+    }
+
+    /// The legal action with the highest expected value.
+    pub fn action(&self, hand: &[Card], up_card: &Card) -> Action {
+        best_action(&self.evaluate(hand, up_card))
+    }
+
+    /// The best action, explained by comparing its expected value with the next best.
+    pub fn hint(&self, hand: &[Card], up_card: &Card) -> Hint {
+        let mut actions = self.evaluate(hand, up_card);
+        actions.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let (action, value) = actions[0];
+        let up = value_symbol(card_value(up_card.rank()).unwrap_or(1));
+
+        let mut reason = format!(
+            "{} v {}: {:+.2}",
+            Situation::of(hand, self.joker_rule),
+            up,
+            value
+        );
+
+        if let Some((next, next_value)) = actions.get(1) {
+            reason += &format!(" vs {} {:+.2}", next, next_value);
+        }
+
+        Hint { action, reason }
+    }
+
+    fn hand_state(&self, hand: &[Card]) -> (usize, bool) {
+        if hand.iter().any(Card::is_joker) {
+            return (Game::hand_value(hand, self.joker_rule) as usize, false);
+        }
+
+        let hard = hand.iter().filter_map(|card| card_value(card.rank())).sum();
+        let ace = hand.iter().any(Card::is_ace);
+
+        (hard, ace)
+    }
+
+    fn index(&self, up: usize, hard: usize, ace: bool, cards: usize) -> usize {
+        let cards = self.cards_key(cards);
+        (((up - 1) * 22 + hard) * 2 + ace as usize) * self.card_slots + cards
+    }
+
+    /// Only a charlie cares how many cards are in a hand.
+    fn cards_key(&self, cards: usize) -> usize {
+        match self.rules.charlie {
+            Some(charlie) => cards.min(charlie),
+            None => 0,
+        }
+    }
+
+    fn dealer_from(&self, hard: usize, ace: bool, cards: usize) -> DealerOutcomes {
+        let mut outcomes = [0.0; 7];
+        let total = soft_total(hard, ace);
+
+        if hard > 21 {
+            outcomes[DEALER_BUST] = 1.0;
+        } else if self.rules.charlie == Some(cards) {
+            outcomes[DEALER_CHARLIE] = 1.0;
+        } else if cards >= 2
+            && (total > 17 || (total == 17 && !(self.rules.dealer_hits_soft_17 && total != hard)))
+        {
+            outcomes[total - 17] = 1.0;
+        } else {
+            for value in 1..=VALUES {
+                let next = self.dealer_from(hard + value, ace || value == 1, cards + 1);
+
+                for (outcome, chance) in outcomes.iter_mut().zip(next) {
+                    *outcome += self.probabilities[value - 1] * chance;
+                }
+            }
+        }
+
+        outcomes
+    }
+
+    fn stand(&self, dealer: &DealerOutcomes, total: usize, charlie: bool) -> f64 {
+        stand_value(&self.rules, dealer, total, charlie)
+    }
+
+    fn bust(&self, dealer: &DealerOutcomes) -> f64 {
+        bust_value(&self.rules, dealer)
+    }
+
+    /// Value of a hand once no more decisions are taken: bust, charlie or standing.
+    fn resolve(&self, up: usize, hard: usize, ace: bool, cards: usize) -> f64 {
+        let dealer = &self.dealer[up - 1];
+
+        if hard > 21 {
+            self.bust(dealer)
+        } else {
+            let charlie = self.rules.charlie == Some(cards);
+            self.stand(dealer, soft_total(hard, ace), charlie)
+        }
+    }
+
+    fn play_on(&self, up: usize, hard: usize, ace: bool, cards: usize) -> f64 {
+        let total = soft_total(hard, ace);
+
+        if hard > 21 || total == 21 || self.rules.charlie == Some(cards) {
+            return self.resolve(up, hard, ace, cards);
+        }
+
+        let stand = self.stand(&self.dealer[up - 1], total, false);
+        stand.max(self.hit(up, hard, ace, cards))
+    }
+
+    fn best(&self, up: usize, hard: usize, ace: bool, cards: usize) -> f64 {
+        if hard > 21 || self.rules.charlie == Some(cards) {
+            self.resolve(up, hard, ace, cards)
+        } else {
+            self.best[self.index(up, hard, ace, cards)]
+        }
+    }
+
+    fn hit(&self, up: usize, hard: usize, ace: bool, cards: usize) -> f64 {
+        (1..=VALUES)
+            .map(|value| {
+                self.probabilities[value - 1]
+                    * self.best(up, hard + value, ace || value == 1, cards + 1)
+            })
+            .sum()
+    }
+
+    fn double(&self, up: usize, hard: usize, ace: bool, cards: usize) -> f64 {
+        let value: f64 = (1..=VALUES)
+            .map(|value| {
+                self.probabilities[value - 1]
+                    * self.resolve(up, hard + value, ace || value == 1, cards + 1)
+            })
+            .sum();
+
+        2.0 * value
+    }
+
+    fn split(&self, up: usize, pair: usize) -> f64 {
+        let value: f64 = (1..=VALUES)
+            .map(|value| {
+                let ace = pair == 1 || value == 1;
+                let hand = if pair == 1 {
+                    self.resolve(up, pair + value, ace, 2)
+                } else {
+                    self.best(up, pair + value, ace, 2)
+                };
+
+                self.probabilities[value - 1] * hand
+            })
+            .sum();
+
+        2.0 * value
+    }
+}
+
+impl Strategy for BasicStrategy {
+    fn play(&self, hand: &[Card], up_card: &Card, _true_count: f32) -> Action {
+        self.action(hand, up_card)
+    }
+}
+
+/// Expected value per unit bet of standing on a total, or on a charlie, against the dealer's
+/// chances.
+pub fn stand_value(rules: &Rules, dealer: &DealerOutcomes, total: usize, charlie: bool) -> f64 {
+    let win = rules.win_pays as f64;
+
+    if charlie {
+        return (1.0 - dealer[DEALER_CHARLIE]) * win;
+    }
+
+    let mut value = dealer[DEALER_BUST] * win - dealer[DEALER_CHARLIE];
+
+    for (dealer_total, chance) in (17..=21).zip(dealer) {
+        if total > dealer_total {
+            value += chance * win;
+        } else if total < dealer_total {
+            value -= chance;
+        }
+    }
+
+    value
+}
+
+/// Expected value per unit bet of a busted hand, which only pushes against a dealer bust when
+/// the rules say so.
+pub fn bust_value(rules: &Rules, dealer: &DealerOutcomes) -> f64 {
+    if rules.bust_push {
+        -(1.0 - dealer[DEALER_BUST])
+    } else {
+        -1.0
+    }
+}
+
+/// Actions the rules allow for a hand: hit and stand always, the rest on the first two cards.
+pub fn legal_actions(hand: &[Card], rules: &Rules) -> Vec<Action> {
+    let mut actions = vec![Action::Hit, Action::Stand];
+
+    if let [first, second] = hand {
+        if rules.double {
+            actions.push(Action::Double);
+        }
+
+        let pair = matches!(
+            (card_value(first.rank()), card_value(second.rank())),
+            (Some(a), Some(b)) if a == b
+        );
+
+        if rules.split && pair {
+            actions.push(Action::Split);
+        }
+
+        if rules.surrender {
+            actions.push(Action::Surrender);
+        }
+    }
+
+    actions
+}
+
+/// The action with the highest expected value.
+pub fn best_action(actions: &[(Action, f64)]) -> Action {
+    actions
+        .iter()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map_or(Action::Stand, |(action, _)| *action)
+}
+
+fn soft_total(hard: usize, ace: bool) -> usize {
+    if ace && hard + 10 <= 21 {
+        hard + 10
+    } else {
+        hard
+    }
+}
@@ -0,0 +1,42 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    style::Stylize,
+    text::{Line, Text},
+    widgets::{block::Title, Block, Paragraph},
+};
+
+/// An exact bet being typed in, and why the last one typed couldn't go down.
+#[derive(Default)]
+pub struct BetEntry {
+    pub typed: String,
+    pub error: Option<String>,
+}
+
+/// The bet prompt, sized to sit over the middle of `area`.
+pub fn create_bet_entry_widget<'a>(entry: &BetEntry, area: Rect) -> (Paragraph<'a>, Rect) {
+    let mut text = Text::from(Line::from(format!("Bet: {}_", entry.typed)).bold());
+
+    if let Some(error) = &entry.error {
+        text.push_line(Line::from(error.clone()).light_red());
+    }
+
+    let [row] = Layout::vertical([Constraint::Length(4)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [prompt] = Layout::horizontal([Constraint::Length(36)])
+        .flex(Flex::Center)
+        .areas(row);
+
+    let paragraph = Paragraph::new(text).alignment(Alignment::Center).block(
+        Block::bordered()
+            .title("Exact Bet")
+            .title_alignment(Alignment::Center)
+            .title(
+                Title::from("(Enter) Bet, (Esc) Cancel")
+                    .alignment(Alignment::Center)
+                    .position(ratatui::widgets::block::Position::Bottom),
+            ),
+    );
+
+    (paragraph, prompt)
+}
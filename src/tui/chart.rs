@@ -0,0 +1,104 @@
+use ratatui::{
+    layout::Alignment,
+    style::{Color, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Block, Paragraph},
+};
+
+use blackjack::chart::{row_of, Chart, Code, Table};
+use blackjack::strategy::{value_symbol, Action, Situation};
+
+/// Up-cards in the order charts print them, aces last.
+const UP_CARDS: [usize; 10] = [2, 3, 4, 5, 6, 7, 8, 9, 10, 1];
+
+/// Widths of the row labels and the cells, full size and narrow.
+const LABEL_WIDTH: usize = 6;
+const CELL_WIDTH: usize = 4;
+const NARROW_LABEL_WIDTH: usize = 5;
+const NARROW_CELL_WIDTH: usize = 3;
+
+/// Columns a table takes with full size cells, borders included.
+pub const CHART_WIDTH: u16 = (LABEL_WIDTH + CELL_WIDTH * UP_CARDS.len() + 2) as u16;
+
+/// One table of the chart in colour, with the cell for the hand being played highlighted.
+/// Narrow cells fit the table into a compact terminal, scrolled to keep the hand in view.
+pub fn create_chart_widget<'a>(
+    chart: &Chart,
+    table: Table,
+    spot: Option<(Situation, usize)>,
+    narrow: bool,
+    height: u16,
+) -> Paragraph<'a> {
+    let (label_width, cell_width) = if narrow {
+        (NARROW_LABEL_WIDTH, NARROW_CELL_WIDTH)
+    } else {
+        (LABEL_WIDTH, CELL_WIDTH)
+    };
+    let spot = spot.map(|(situation, up)| (row_of(situation), up));
+
+    let mut header = vec![Span::raw(" ".repeat(label_width))];
+    header.extend(
+        UP_CARDS.iter().map(|up| {
+            Span::raw(format!("{:^width$}", value_symbol(*up), width = cell_width)).bold()
+        }),
+    );
+
+    let mut rows = Vec::new();
+    let mut spot_row = None;
+
+    for (index, situation) in table.rows().into_iter().enumerate() {
+        let mut row = vec![Span::raw(format!(
+            "{:>width$} ",
+            Table::label(situation),
+            width = label_width - 1
+        ))
+        .bold()];
+
+        for up in UP_CARDS {
+            let code = chart.code(situation, up);
+            let cell = code.map_or("-".to_string(), |code| code.to_string());
+            let mut span = Span::styled(
+                format!("{:^width$}", cell, width = cell_width),
+                code.map_or(Style::default(), style_of),
+            );
+
+            if spot == Some((situation, up)) {
+                span = span.reversed().bold().underlined();
+                spot_row = Some(index);
+            }
+
+            row.push(span);
+        }
+
+        rows.push(Line::from(row));
+    }
+
+    // Borders and the header take three lines, the header staying put as the rows scroll.
+    let visible = height.saturating_sub(3) as usize;
+    let scroll = match spot_row {
+        Some(row) if narrow && row >= visible => row + 1 - visible,
+        _ => 0,
+    };
+
+    let mut text = Text::from(Line::from(header));
+    text.extend(rows.into_iter().skip(scroll));
+
+    Paragraph::new(text).alignment(Alignment::Center).block(
+        Block::bordered()
+            .title(table.name())
+            .title_alignment(Alignment::Center),
+    )
+}
+
+/// Colours by the action a code starts with.
+fn style_of(code: Code) -> Style {
+    let background = match code.action() {
+        Action::Hit => Color::LightRed,
+        Action::Stand => Color::LightYellow,
+        Action::Double => Color::LightGreen,
+        Action::Split => Color::LightCyan,
+        Action::Surrender => Color::Gray,
+    };
+
+    Style::default().fg(Color::Black).bg(background)
+}
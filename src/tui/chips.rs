@@ -0,0 +1,86 @@
+use ratatui::{
+    layout::Alignment,
+    style::{Color, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{block::Title, Block, Paragraph},
+};
+
+use blackjack::game::chip_stack;
+use blackjack::money::Money;
+
+use super::Game;
+
+/// Lines the chip stack takes, borders and any money no chip covers included.
+pub const CHIPS_HEIGHT: u16 = 7;
+
+/// Casino colours for the chips in the tray, smallest first.
+const COLOURS: [Color; 4] = [
+    Color::LightRed,
+    Color::LightGreen,
+    Color::White,
+    Color::LightMagenta,
+];
+
+/// The chip tray on one line for a compact terminal: each key, chip and the ones of it in the
+/// bet, then the clear and last bet keys.
+pub fn create_chips_line<'a>(game: &Game) -> Line<'a> {
+    let (stack, left) = chip_stack(game.bet());
+    let mut line = vec![Span::raw("Chips:")];
+
+    for (key, (chip, count)) in stack.iter().rev().enumerate() {
+        let separator = if key == 0 { " " } else { ", " };
+
+        line.push(Span::raw(format!("{}{} {}", separator, key + 1, chip)));
+
+        if *count > 0 {
+            line.push(Span::styled(
+                format!(" {}", "●".repeat(*count)),
+                Style::default().fg(COLOURS[key]).bold(),
+            ));
+        }
+    }
+
+    if left > Money::ZERO {
+        line.push(Span::raw(format!(", + {}", left)));
+    }
+
+    line.push(Span::raw(format!(
+        " | (X) Clear, (L)ast {}",
+        game.last_bet()
+    )));
+
+    Line::from(line)
+}
+
+/// The chip tray with the keys that add each chip, and the chips making up the bet stacked next
+/// to them.
+pub fn create_chips_widget<'a>(game: &Game) -> Paragraph<'a> {
+    let (stack, left) = chip_stack(game.bet());
+    let mut text = Text::default();
+
+    // The tray keys go up from the smallest chip, the stack down from the largest.
+    for (key, (chip, count)) in stack.iter().rev().enumerate().rev() {
+        let mut line = vec![Span::raw(format!("{} {:>4} ", key + 1, chip))];
+        line.push(Span::styled(
+            "●".repeat(*count),
+            Style::default().fg(COLOURS[key]).bold(),
+        ));
+
+        text.push_line(Line::from(line));
+    }
+
+    if left > Money::ZERO {
+        text.push_line(format!("+ {}", left));
+    }
+
+    Paragraph::new(text).block(
+        Block::bordered()
+            .title("Chips (1-4)")
+            .title_alignment(Alignment::Center)
+            .title(
+                Title::from(format!("(X) Clear, (L)ast {}", game.last_bet()))
+                    .alignment(Alignment::Center)
+                    .position(ratatui::widgets::block::Position::Bottom),
+            ),
+    )
+}
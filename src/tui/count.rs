@@ -0,0 +1,30 @@
+use ratatui::{
+    layout::Alignment,
+    widgets::{Block, Paragraph},
+};
+
+use super::Game;
+
+pub fn create_count_widget(game: &Game) -> Paragraph<'_> {
+    let mut text = format!("Running count: {:+}\n", game.running_count());
+
+    // Unbalanced counts are played off the running count against their key count.
+    text += &match game.key_count() {
+        Some(key_count) if !game.counting_system().is_balanced() => format!(
+            "Key count: {:+}{}",
+            key_count,
+            if game.running_count() >= key_count {
+                " ✓"
+            } else {
+                ""
+            }
+        ),
+        _ => format!("True count: {:+.1}", game.true_count()),
+    };
+
+    Paragraph::new(text).alignment(Alignment::Center).block(
+        Block::bordered()
+            .title(game.counting_system().name())
+            .title_alignment(Alignment::Center),
+    )
+}
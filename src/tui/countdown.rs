@@ -0,0 +1,95 @@
+use ratatui::{
+    layout::Alignment,
+    style::Stylize,
+    text::{Line, Text},
+    widgets::{block::Title, Block, Paragraph},
+};
+
+use super::{hand_as_string, DrawMode};
+use blackjack::accuracy::percentage;
+use blackjack::countdown::{Countdown, Phase};
+
+pub fn create_countdown_widget<'a>(
+    countdown: &Countdown,
+    typed: &str,
+    draw_mode: DrawMode,
+) -> Paragraph<'a> {
+    let mut text = Text::default();
+
+    let keys = match countdown.phase() {
+        Phase::Dealing => {
+            text.push_line("");
+            text.extend(Text::from(hand_as_string(countdown.showing(), draw_mode)));
+            text.push_line(format!("Cards left: {}", countdown.cards_left()));
+
+            "(Q)uit, (Space) next card"
+        }
+        Phase::Answering => {
+            text.push_line("");
+            text.push_line(Line::from("Running count?").bold());
+            text.push_line(format!("{}_", typed));
+
+            "(Q)uit, (Enter) answer"
+        }
+        Phase::Done => {
+            if let Some(run) = countdown.runs().last() {
+                text.push_line("");
+
+                if run.is_correct() {
+                    text.push_line(
+                        Line::from(format!("Right, {:+}", run.running_count)).light_green(),
+                    );
+                } else {
+                    text.push_line(
+                        Line::from(format!(
+                            "Wrong, the count was {:+} not {:+}",
+                            run.running_count, run.answer
+                        ))
+                        .light_red(),
+                    );
+                }
+
+                let seconds = run.time.as_secs_f32();
+                text.push_line(format!(
+                    "{:.1}s for {} cards ({:.2}s a card)",
+                    seconds,
+                    run.cards,
+                    seconds / run.cards as f32
+                ));
+
+                let held_back: Vec<String> = countdown
+                    .held_back()
+                    .iter()
+                    .map(|card| card.code())
+                    .collect();
+                text.push_line(format!("Held back: {}", held_back.join(" ")));
+            }
+
+            let (correct, runs) = countdown.accuracy();
+            text.push_line("");
+            text.push_line(format!(
+                "Session: {} of {} right ({:.0}%)",
+                correct,
+                runs,
+                percentage(correct, runs)
+            ));
+
+            if let Some(best) = countdown.best_time() {
+                text.push_line(format!("Best time: {:.1}s", best.as_secs_f32()));
+            }
+
+            "(Q)uit, (N)ew deck"
+        }
+    };
+
+    Paragraph::new(text).alignment(Alignment::Center).block(
+        Block::bordered()
+            .title(format!("Countdown: {}", countdown.system_name()))
+            .title_alignment(Alignment::Center)
+            .title(
+                Title::from(keys)
+                    .alignment(Alignment::Center)
+                    .position(ratatui::widgets::block::Position::Bottom),
+            ),
+    )
+}
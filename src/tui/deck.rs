@@ -3,13 +3,47 @@ use ratatui::{
     widgets::{Block, Paragraph},
 };
 
-use super::PLAYING_DECK_SIZE;
+use super::Shoe;
+use blackjack::card::RANKS;
 
-pub fn create_deck_widget<'a>(cards_remaining: usize, layout_area: Rect) -> Paragraph<'a> {
-    let mut paragraph_deck = format!("Cards remaining: {}\n\n", cards_remaining);
+const RANK_BREAKDOWN_MIN_HEIGHT: u16 = 35;
 
-    let deck_height = layout_area.height - 4;
-    let cutoff = 1.0 - (cards_remaining as f32 / PLAYING_DECK_SIZE as f32);
+pub fn create_deck_widget<'a>(shoe: &Shoe, layout_area: Rect) -> Paragraph<'a> {
+    let cards_remaining = shoe.cards_remaining();
+
+    let mut paragraph_deck = format!(
+        "Cards remaining: {}\nDiscarded: {}\nShoe: {:.8}\n\n",
+        cards_remaining,
+        shoe.discard_len(),
+        shoe.commitment()
+    );
+
+    let mut deck_height = layout_area.height - 6;
+
+    if layout_area.height >= RANK_BREAKDOWN_MIN_HEIGHT {
+        let unseen = shoe.unseen_counts();
+        let seen = shoe.seen_counts();
+
+        // Leave out ranks this shoe does not have, like jokers in a standard composition.
+        let ranks: Vec<_> = RANKS
+            .iter()
+            .filter(|rank| unseen[rank.index()] + seen[rank.index()] > 0)
+            .collect();
+
+        for rank in &ranks {
+            paragraph_deck += &format!(
+                "{:<2} {:>3} left {:>3} seen\n",
+                rank.symbol(),
+                unseen[rank.index()],
+                seen[rank.index()]
+            );
+        }
+
+        paragraph_deck += "\n";
+        deck_height -= ranks.len() as u16 + 1;
+    }
+
+    let cutoff = 1.0 - (cards_remaining as f32 / shoe.size() as f32);
 
     let bar = "█".repeat((layout_area.width - 4) as usize);
 
@@ -0,0 +1,107 @@
+use ratatui::{
+    layout::Alignment,
+    style::Stylize,
+    text::{Line, Text},
+    widgets::{block::Title, Block, Paragraph},
+};
+
+use super::DrawMode;
+use blackjack::accuracy::percentage;
+use blackjack::deviation::{DeviationTrainer, Phase};
+
+/// The true count to play the hand at, the score so far and how the last answer went.
+pub fn create_deviation_info_widget(
+    trainer: &DeviationTrainer,
+    draw_mode: DrawMode,
+) -> Paragraph<'_> {
+    let (answered, correct) = trainer.score();
+
+    let mut text = Text::from(
+        Line::from(format!(
+            "True count: {:+}   Score: {}/{}",
+            trainer.true_count(),
+            correct,
+            answered
+        ))
+        .bold(),
+    );
+
+    let keys = match trainer.phase() {
+        Phase::Choosing => match draw_mode {
+            DrawMode::Compact => "q|h|s|d|p|r",
+            _ => "(Q)uit, (H)it, (S)tand, (D)ouble, s(P)lit, su(R)render",
+        },
+        Phase::Answered { played, correct } => {
+            if correct {
+                text.push_line(Line::from(format!("Right, {}", played)).light_green());
+            } else {
+                text.push_line(
+                    Line::from(format!(
+                        "Wrong, {} instead of {}",
+                        played,
+                        trainer.right_play()
+                    ))
+                    .light_red(),
+                );
+            }
+
+            for deviation in trainer.spot_deviations() {
+                text.push_line(deviation.to_string());
+            }
+
+            match draw_mode {
+                DrawMode::Compact => "q|any key",
+                _ => "(Q)uit, any other key for the next hand",
+            }
+        }
+    };
+
+    Paragraph::new(text).alignment(Alignment::Center).block(
+        Block::bordered()
+            .title("Deviation Trainer")
+            .title_alignment(Alignment::Center)
+            .title(
+                Title::from(keys)
+                    .alignment(Alignment::Center)
+                    .position(ratatui::widgets::block::Position::Bottom),
+            ),
+    )
+}
+
+pub fn create_deviation_report_widget(trainer: &DeviationTrainer) -> Paragraph<'_> {
+    let (answered, correct) = trainer.score();
+
+    let mut text = Text::default();
+
+    text.push_line(Line::from("Index plays").bold());
+    text.push_line(format!(
+        "{} of {} right ({:.1}%)",
+        correct,
+        answered,
+        percentage(correct, answered)
+    ));
+    text.push_line("");
+
+    let misses = trainer.misses();
+
+    if misses.is_empty() {
+        text.push_line(Line::from("No missed deviations").light_green());
+    } else {
+        text.push_line(Line::from("Missed deviations").bold());
+
+        for miss in misses {
+            text.push_line(Line::from(format!("{}x {}", miss.count, miss.deviation)).light_red());
+        }
+    }
+
+    Paragraph::new(text).alignment(Alignment::Center).block(
+        Block::bordered()
+            .title("Session Report")
+            .title_alignment(Alignment::Center)
+            .title(
+                Title::from("(Q)uit, any other key to go back")
+                    .alignment(Alignment::Center)
+                    .position(ratatui::widgets::block::Position::Bottom),
+            ),
+    )
+}
@@ -0,0 +1,81 @@
+use ratatui::{
+    layout::Alignment,
+    style::Stylize,
+    text::{Line, Text},
+    widgets::{block::Title, Block, Paragraph},
+};
+
+use super::DrawMode;
+use blackjack::accuracy::percentage;
+use blackjack::flashcard::{Flashcards, Phase};
+use blackjack::strategy::Action;
+
+/// The score so far, the actions to pick from and the grade of the last answer.
+pub fn create_flashcard_info_widget(flashcards: &Flashcards, draw_mode: DrawMode) -> Paragraph<'_> {
+    let (answered, correct) = flashcards.accuracy().total();
+
+    let mut text = Text::from(
+        Line::from(format!(
+            "Score: {}/{} ({:.0}%)",
+            correct,
+            answered,
+            percentage(correct, answered)
+        ))
+        .bold(),
+    );
+
+    let keys = match flashcards.phase() {
+        Phase::Choosing => {
+            let keys: Vec<&str> = flashcards
+                .legal_actions()
+                .iter()
+                .map(|action| match (action, draw_mode) {
+                    (Action::Hit, DrawMode::Compact) => "h",
+                    (Action::Stand, DrawMode::Compact) => "s",
+                    (Action::Double, DrawMode::Compact) => "d",
+                    (Action::Split, DrawMode::Compact) => "p",
+                    (Action::Surrender, DrawMode::Compact) => "r",
+                    (Action::Hit, _) => "(H)it",
+                    (Action::Stand, _) => "(S)tand",
+                    (Action::Double, _) => "(D)ouble",
+                    (Action::Split, _) => "s(P)lit",
+                    (Action::Surrender, _) => "su(R)render",
+                })
+                .collect();
+
+            match draw_mode {
+                DrawMode::Compact => format!("q|{}", keys.join("|")),
+                _ => format!("(Q)uit, {}", keys.join(", ")),
+            }
+        }
+        Phase::Answered { played, correct } => {
+            let hint = flashcards.hint();
+
+            if correct {
+                text.push_line(Line::from(format!("Right, {}", played)).light_green());
+            } else {
+                text.push_line(
+                    Line::from(format!("Wrong, {} instead of {}", played, hint.action)).light_red(),
+                );
+            }
+
+            text.push_line(hint.reason);
+
+            match draw_mode {
+                DrawMode::Compact => "q|any key".to_string(),
+                _ => "(Q)uit, any other key for the next hand".to_string(),
+            }
+        }
+    };
+
+    Paragraph::new(text).alignment(Alignment::Center).block(
+        Block::bordered()
+            .title("Flashcards")
+            .title_alignment(Alignment::Center)
+            .title(
+                Title::from(keys)
+                    .alignment(Alignment::Center)
+                    .position(ratatui::widgets::block::Position::Bottom),
+            ),
+    )
+}
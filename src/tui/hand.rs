@@ -1,28 +1,92 @@
+use std::time::Duration;
+
 use ratatui::{
     layout::Alignment,
     style::Stylize,
+    text::{Line, Text},
     widgets::{block::Title, Block, Paragraph},
 };
 
 use super::{Card, DrawMode, Game, State, Winner};
+use blackjack::game::MIN_BET;
+use blackjack::strategy::best_action;
 
-pub fn create_hand_widgets(game: &Game, draw_mode: DrawMode) -> (Paragraph, Paragraph) {
+pub fn create_hand_widgets(
+    game: &Game,
+    draw_mode: DrawMode,
+    show_hint: bool,
+    show_values: bool,
+    values_working: Option<Duration>,
+) -> (Paragraph<'_>, Paragraph<'_>) {
     let title1 = Title::from("My Hand").alignment(Alignment::Center);
     let title2 = Title::from(match draw_mode {
         DrawMode::Warn => "",
         DrawMode::Compact => match game.state() {
-            State::MyTurn => "q|h|s",
-            State::Result(_) => "q|n|i|d",
+            State::MyTurn => "q|h|s|?",
+            State::Result(_) => "q|n|i|d|1-4|x|l|b",
+            State::Broke(_) => "q|r",
         },
         DrawMode::NormalCompact | DrawMode::Normal => match game.state() {
-            State::MyTurn => "(Q)uit, (H)it, (S)tay",
-            State::Result(_) => "(Q)uit, (N)ew, (i/d): ↑/↓ Bet",
+            State::MyTurn => "(Q)uit, (H)it, (S)tay, (?) Hint",
+            State::Result(_) => "(Q)uit, (N)ew, (i/d) ↑/↓, (B)et",
+            State::Broke(_) => "(Q)uit, (R)ebuy",
         },
     })
     .alignment(Alignment::Center)
     .position(ratatui::widgets::block::Position::Bottom);
 
-    let paragraph_me = Paragraph::new(hand_as_string(game.my_hand(), draw_mode))
+    let mut text_me = Text::from(hand_as_string(game.my_hand(), draw_mode));
+
+    // The hint goes below the cards so it never pushes them out of view.
+    if let (true, Some(hint)) = (show_hint, game.hint()) {
+        text_me.push_line("");
+        text_me.push_line(Line::from(format!("Hint: {}", hint.action)).yellow().bold());
+        text_me.push_line(Line::from(hint.reason).yellow());
+    }
+
+    if let (true, Some(values)) = (show_values, game.action_values()) {
+        let best = best_action(values);
+
+        text_me.push_line("");
+        text_me.push_line(Line::from("Exact EV").cyan().bold());
+
+        for (action, value) in values {
+            let line = Line::from(format!("{} {:+.3}", action, value)).cyan();
+
+            text_me.push_line(if *action == best { line.bold() } else { line });
+        }
+    } else if let (true, Some(working)) = (show_values, values_working) {
+        text_me.push_line("");
+        text_me.push_line(Line::from("Exact EV").cyan().bold());
+        text_me.push_line(Line::from(format!("Working it out... {}s", working.as_secs())).cyan());
+    }
+
+    if let State::Result(_) = game.state() {
+        if game.is_under_minimum() {
+            text_me.push_line("");
+            text_me.push_line(Line::from(format!("Bet at least {} to deal", MIN_BET)).light_red());
+        } else if game.is_off_ramp() {
+            text_me.push_line("");
+            text_me.push_line(
+                Line::from(format!(
+                    "Bet {} is far off the ramp's {}",
+                    game.bet(),
+                    game.advised_bet()
+                ))
+                .light_red(),
+            );
+        }
+    }
+
+    if let (State::Broke(_), Some(balance)) = (game.state(), game.balance()) {
+        text_me.push_line("");
+        text_me.push_line(Line::from("Out of money").light_red().bold());
+        text_me.push_line(
+            Line::from(format!("{} left won't cover the minimum bet", balance)).light_red(),
+        );
+    }
+
+    let paragraph_me = Paragraph::new(text_me)
         .alignment(Alignment::Center)
         .block(Block::bordered().title(title1).title(title2));
 
@@ -35,14 +99,41 @@ pub fn create_hand_widgets(game: &Game, draw_mode: DrawMode) -> (Paragraph, Para
         );
 
     match game.state() {
-        State::Result(Winner::None) => (paragraph_me.light_blue(), paragraph_dealer.light_blue()),
-        State::Result(Winner::Me) => (paragraph_me.light_green(), paragraph_dealer.light_red()),
-        State::Result(Winner::Dealer) => (paragraph_me.light_red(), paragraph_dealer.light_green()),
-        _ => (paragraph_me, paragraph_dealer),
+        State::Result(winner) | State::Broke(winner) => match winner {
+            Winner::None => (paragraph_me.light_blue(), paragraph_dealer.light_blue()),
+            Winner::Me => (paragraph_me.light_green(), paragraph_dealer.light_red()),
+            Winner::Dealer => (paragraph_me.light_red(), paragraph_dealer.light_green()),
+        },
+        State::MyTurn => (paragraph_me, paragraph_dealer),
     }
 }
 
-fn hand_as_string(hand: &Vec<Card>, draw_mode: DrawMode) -> String {
+/// A hand against a lone dealer up-card, for the drills.
+pub fn create_spot_widgets<'a>(
+    hand: &[Card],
+    up_card: &[Card],
+    draw_mode: DrawMode,
+) -> (Paragraph<'a>, Paragraph<'a>) {
+    let paragraph_me = Paragraph::new(hand_as_string(hand, draw_mode))
+        .alignment(Alignment::Center)
+        .block(
+            Block::bordered()
+                .title("My Hand")
+                .title_alignment(Alignment::Center),
+        );
+
+    let paragraph_dealer = Paragraph::new(hand_as_string(up_card, draw_mode))
+        .alignment(Alignment::Center)
+        .block(
+            Block::bordered()
+                .title("Dealer Up-Card")
+                .title_alignment(Alignment::Center),
+        );
+
+    (paragraph_me, paragraph_dealer)
+}
+
+pub(super) fn hand_as_string(hand: &[Card], draw_mode: DrawMode) -> String {
     let mut output = String::new();
 
     match draw_mode {
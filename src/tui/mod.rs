@@ -1,24 +1,63 @@
-use std::io::Stdout;
+use std::{io::Stdout, time::Duration};
 
 use ratatui::{
     backend::CrosstermBackend,
-    layout::{Alignment, Constraint, Direction, Layout},
-    widgets::{Block, Paragraph},
-    Terminal,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    widgets::{block::Title, Block, Clear, Paragraph},
+    Frame, Terminal,
 };
 
-use crate::card::Card;
-use crate::game::{Game, State, Winner, PLAYING_DECK_SIZE};
+use blackjack::card::Card;
+use blackjack::chart::{row_of, Chart, Table, TABLES};
+use blackjack::config::Config;
+use blackjack::countdown::Countdown;
+use blackjack::deviation::DeviationTrainer;
+use blackjack::flashcard::Flashcards;
+use blackjack::game::{Game, State, Winner};
+use blackjack::money::Money;
+use blackjack::shoe::Shoe;
+use blackjack::simulate::{Results, Simulation};
+use blackjack::strategy::{BasicStrategy, Situation};
 
+mod bet;
+use bet::create_bet_entry_widget;
+pub use bet::BetEntry;
+mod chart;
+use chart::{create_chart_widget, CHART_WIDTH};
+
+mod chips;
+use chips::{create_chips_widget, CHIPS_HEIGHT};
+mod count;
+use count::create_count_widget;
+mod countdown;
+use countdown::create_countdown_widget;
 mod deck;
 use deck::create_deck_widget;
+mod deviation;
+use deviation::{create_deviation_info_widget, create_deviation_report_widget};
+mod flashcard;
+use flashcard::create_flashcard_info_widget;
 mod hand;
-use hand::create_hand_widgets;
+use hand::{create_hand_widgets, create_spot_widgets, hand_as_string};
+mod odds;
+use odds::{create_odds_widget, ODDS_HEIGHT};
 mod profit;
 use profit::create_profit_widget;
+mod report;
+use report::create_report_widget;
+mod risk;
+use risk::create_risk_widget;
 mod stats;
 use stats::create_stats_widget;
 
+/// The ramp's bet, only while betting on the next hand.
+fn advised_bet(game: &Game) -> Option<Money> {
+    match game.state() {
+        State::Result(_) => Some(game.advised_bet()),
+        State::MyTurn | State::Broke(_) => None,
+    }
+}
+
 #[derive(Clone, Copy)]
 enum DrawMode {
     Warn,
@@ -27,6 +66,7 @@ enum DrawMode {
     Normal,
 }
 
+#[allow(clippy::upper_case_acronyms)]
 pub struct TUI<'a> {
     terminal: Terminal<CrosstermBackend<Stdout>>,
     paragraph_title: Paragraph<'a>,
@@ -37,10 +77,20 @@ pub struct TUI<'a> {
     table_compact: Layout,
     warning: Paragraph<'a>,
     draw_mode: DrawMode,
+    always_hint: bool,
+    hint_requested: bool,
+    showing_report: bool,
+    showing_count: bool,
+    showing_odds: bool,
+    showing_values: bool,
+    showing_chart: bool,
+    /// The configured chart, or the strategy engine's plays as one.
+    chart: Chart,
+    chart_name: &'static str,
 }
 
 impl<'a> TUI<'a> {
-    pub fn new() -> Self {
+    pub fn new(config: &Config) -> Self {
         let terminal = ratatui::init();
 
         let title = vec![
@@ -65,7 +115,7 @@ impl<'a> TUI<'a> {
             .constraints(vec![
                 Constraint::Length(1),
                 Constraint::Percentage(100),
-                Constraint::Length(4),
+                Constraint::Length(5),
             ]);
 
         let table = Layout::default()
@@ -99,24 +149,294 @@ impl<'a> TUI<'a> {
             table_compact,
             warning,
             draw_mode: DrawMode::Normal,
+            always_hint: config.always_hint,
+            hint_requested: false,
+            showing_report: false,
+            showing_count: false,
+            showing_odds: false,
+            showing_values: false,
+            showing_chart: false,
+            chart: config.chart.clone().unwrap_or_else(|| {
+                Chart::from_strategy(&BasicStrategy::new(&config.rules, &config.composition))
+            }),
+            chart_name: match config.chart {
+                Some(_) => "Strategy Chart",
+                None => "Basic Strategy",
+            },
+        }
+    }
+
+    /// Shows the basic strategy hint until the next action.
+    pub fn request_hint(&mut self) {
+        self.hint_requested = true;
+    }
+
+    pub fn clear_hint(&mut self) {
+        self.hint_requested = false;
+    }
+
+    /// Swaps the table for the session report, or back again.
+    pub fn toggle_report(&mut self) {
+        self.showing_report = !self.showing_report;
+    }
+
+    pub fn toggle_count(&mut self) {
+        self.showing_count = !self.showing_count;
+    }
+
+    pub fn toggle_odds(&mut self) {
+        self.showing_odds = !self.showing_odds;
+    }
+
+    /// Shows the exact expected value of each action on the player's turn, or hides it.
+    pub fn toggle_values(&mut self) {
+        self.showing_values = !self.showing_values;
+    }
+
+    /// Swaps the table for the strategy chart, or back again.
+    pub fn toggle_chart(&mut self) {
+        self.showing_chart = !self.showing_chart;
+    }
+
+    pub fn showing_values(&self) -> bool {
+        self.showing_values
+    }
+
+    pub fn showing_report(&self) -> bool {
+        self.showing_report
+    }
+
+    fn draw_mode_for(area: Rect) -> DrawMode {
+        if area.width < 26 || area.height < 12 {
+            DrawMode::Warn
+        } else if area.width < 130 || area.height < 31 {
+            DrawMode::Compact
+        } else if area.height < 45 {
+            DrawMode::NormalCompact
+        } else {
+            DrawMode::Normal
         }
     }
 
-    pub fn draw(&mut self, game: &Game) -> std::io::Result<()> {
+    /// Draws the countdown drill, with the answer typed so far.
+    pub fn draw_countdown(&mut self, countdown: &Countdown, typed: &str) -> std::io::Result<()> {
         self.terminal.draw(|frame| {
             let area = frame.area();
+            self.draw_mode = Self::draw_mode_for(area);
+
+            // Two cards sit side by side, like the hands do when space is short.
+            let cards_mode = match self.draw_mode {
+                DrawMode::Normal => DrawMode::NormalCompact,
+                draw_mode => draw_mode,
+            };
 
-            if area.width < 26 || area.height < 12 {
-                self.draw_mode = DrawMode::Warn;
-            } else if area.width < 130 || area.height < 31 {
-                self.draw_mode = DrawMode::Compact;
-            } else if area.height < 45 {
-                self.draw_mode = DrawMode::NormalCompact;
-            } else {
-                self.draw_mode = DrawMode::Normal;
+            match self.draw_mode {
+                DrawMode::Warn => {
+                    frame.render_widget(&self.warning, area);
+                }
+                DrawMode::Compact => {
+                    let main_layout = self.main_layout_compact.split(area);
+
+                    frame.render_widget(&self.paragraph_title_compact, main_layout[0]);
+                    frame.render_widget(
+                        create_countdown_widget(countdown, typed, cards_mode),
+                        main_layout[1].union(main_layout[2]),
+                    );
+                }
+                DrawMode::NormalCompact | DrawMode::Normal => {
+                    let main_layout = self.main_layout.split(area);
+
+                    frame.render_widget(&self.paragraph_title, main_layout[0]);
+                    frame.render_widget(
+                        create_countdown_widget(countdown, typed, cards_mode),
+                        main_layout[1],
+                    );
+                }
             }
+        })?;
+
+        Ok(())
+    }
+
+    /// Draws the deviation trainer, or its session report.
+    pub fn draw_deviations(&mut self, trainer: &DeviationTrainer) -> std::io::Result<()> {
+        self.draw_spot(
+            trainer.hand(),
+            trainer.up_card(),
+            |draw_mode| create_deviation_info_widget(trainer, draw_mode),
+            || create_deviation_report_widget(trainer),
+        )
+    }
 
-            let (my_hand_widget, dealer_hand_widget) = create_hand_widgets(game, self.draw_mode);
+    /// Draws the flashcard drill, or its accuracy report.
+    pub fn draw_flashcards(&mut self, flashcards: &Flashcards) -> std::io::Result<()> {
+        self.draw_spot(
+            flashcards.hand(),
+            flashcards.up_card(),
+            |draw_mode| create_flashcard_info_widget(flashcards, draw_mode),
+            || create_report_widget(flashcards.accuracy(), false),
+        )
+    }
+
+    /// Draws the risk of ruin report, or a note that the simulation is running until there
+    /// are results.
+    pub fn draw_risk(
+        &mut self,
+        simulation: &Simulation,
+        results: Option<&Results>,
+        batches_done: usize,
+    ) -> std::io::Result<()> {
+        self.terminal.draw(|frame| {
+            let area = frame.area();
+            self.draw_mode = Self::draw_mode_for(area);
+
+            let body = match self.draw_mode {
+                DrawMode::Warn => {
+                    frame.render_widget(&self.warning, area);
+                    return;
+                }
+                DrawMode::Compact => {
+                    let main_layout = self.main_layout_compact.split(area);
+                    frame.render_widget(&self.paragraph_title_compact, main_layout[0]);
+
+                    main_layout[1].union(main_layout[2])
+                }
+                DrawMode::NormalCompact | DrawMode::Normal => {
+                    let main_layout = self.main_layout.split(area);
+                    frame.render_widget(&self.paragraph_title, main_layout[0]);
+
+                    main_layout[1]
+                }
+            };
+
+            frame.render_widget(create_risk_widget(simulation, results, batches_done), body);
+        })?;
+
+        Ok(())
+    }
+
+    /// Renders the chart's three tables side by side, or in a compact terminal only the table
+    /// the hand being played is in.
+    fn render_chart(
+        frame: &mut Frame,
+        area: Rect,
+        chart: &Chart,
+        name: &str,
+        spot: Option<(Situation, usize)>,
+        draw_mode: DrawMode,
+    ) {
+        if let DrawMode::Compact = draw_mode {
+            let table = spot.map_or(Table::Hard, |(situation, _)| Table::of(row_of(situation)));
+            frame.render_widget(
+                create_chart_widget(chart, table, spot, true, area.height),
+                area,
+            );
+            return;
+        }
+
+        let block = Block::bordered()
+            .title(name)
+            .title_alignment(Alignment::Center)
+            .title(
+                Title::from("(V) Back to the table")
+                    .alignment(Alignment::Center)
+                    .position(ratatui::widgets::block::Position::Bottom),
+            );
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Ratio(1, 3); TABLES.len()])
+            .split(inner);
+
+        // Until the terminal is wide enough for three full tables, the cells narrow.
+        for (table, column) in TABLES.iter().zip(columns.iter()) {
+            frame.render_widget(
+                create_chart_widget(
+                    chart,
+                    *table,
+                    spot,
+                    column.width < CHART_WIDTH,
+                    column.height,
+                ),
+                *column,
+            );
+        }
+    }
+
+    /// Draws a drill of a hand against an up-card with a panel of information below, or the
+    /// drill's report in its place.
+    fn draw_spot<'b>(
+        &mut self,
+        hand: &[Card],
+        up_card: &[Card],
+        info: impl Fn(DrawMode) -> Paragraph<'b>,
+        report: impl Fn() -> Paragraph<'b>,
+    ) -> std::io::Result<()> {
+        self.terminal.draw(|frame| {
+            let area = frame.area();
+            self.draw_mode = Self::draw_mode_for(area);
+
+            let body = match self.draw_mode {
+                DrawMode::Warn => {
+                    frame.render_widget(&self.warning, area);
+                    return;
+                }
+                DrawMode::Compact => {
+                    let main_layout = self.main_layout_compact.split(area);
+                    frame.render_widget(&self.paragraph_title_compact, main_layout[0]);
+
+                    main_layout[1].union(main_layout[2])
+                }
+                DrawMode::NormalCompact | DrawMode::Normal => {
+                    let main_layout = self.main_layout.split(area);
+                    frame.render_widget(&self.paragraph_title, main_layout[0]);
+
+                    main_layout[1]
+                }
+            };
+
+            if self.showing_report {
+                frame.render_widget(report(), body);
+                return;
+            }
+
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(vec![Constraint::Min(0), Constraint::Length(6)])
+                .split(body);
+            let table = self.table_compact.split(rows[0]);
+
+            let (my_hand_widget, dealer_widget) =
+                create_spot_widgets(hand, up_card, self.draw_mode);
+            frame.render_widget(my_hand_widget, table[0]);
+            frame.render_widget(dealer_widget, table[1]);
+            frame.render_widget(info(self.draw_mode), rows[1]);
+        })?;
+
+        Ok(())
+    }
+
+    /// Draws the table, with the bet prompt over it while an exact bet is being typed, and how
+    /// long the exact values have been worked on while they are.
+    pub fn draw(
+        &mut self,
+        game: &Game,
+        bet_entry: Option<&BetEntry>,
+        values_working: Option<Duration>,
+    ) -> std::io::Result<()> {
+        self.terminal.draw(|frame| {
+            let area = frame.area();
+            self.draw_mode = Self::draw_mode_for(area);
+
+            let (my_hand_widget, dealer_hand_widget) = create_hand_widgets(
+                game,
+                self.draw_mode,
+                self.always_hint || self.hint_requested,
+                self.showing_values,
+                values_working,
+            );
 
             match self.draw_mode {
                 DrawMode::Warn => {
@@ -127,13 +447,42 @@ impl<'a> TUI<'a> {
 
                     frame.render_widget(&self.paragraph_title_compact, main_layout[0]);
 
-                    let table = self.table_compact.split(main_layout[1]);
+                    if self.showing_report {
+                        frame.render_widget(
+                            create_report_widget(game.accuracy(), true),
+                            main_layout[1],
+                        );
+                    } else if self.showing_chart {
+                        Self::render_chart(
+                            frame,
+                            main_layout[1],
+                            &self.chart,
+                            self.chart_name,
+                            game.spot(),
+                            self.draw_mode,
+                        );
+                    } else {
+                        let table = self.table_compact.split(main_layout[1]);
+                        let mut dealer_area = table[1];
+
+                        if self.showing_odds {
+                            let column = Layout::default()
+                                .direction(Direction::Vertical)
+                                .constraints(vec![
+                                    Constraint::Min(0),
+                                    Constraint::Length(ODDS_HEIGHT),
+                                ])
+                                .split(table[1]);
 
-                    frame.render_widget(my_hand_widget, table[0]);
-                    frame.render_widget(dealer_hand_widget, table[1]);
+                            frame.render_widget(create_odds_widget(game), column[1]);
+                            dealer_area = column[0];
+                        }
 
-                    let stats_widget =
-                        create_stats_widget(game.profit(), game.bet(), game.deck_length());
+                        frame.render_widget(my_hand_widget, table[0]);
+                        frame.render_widget(dealer_hand_widget, dealer_area);
+                    }
+
+                    let stats_widget = create_stats_widget(game, self.showing_count);
                     frame.render_widget(stats_widget, main_layout[2]);
                 }
                 DrawMode::NormalCompact | DrawMode::Normal => {
@@ -141,18 +490,78 @@ impl<'a> TUI<'a> {
 
                     frame.render_widget(&self.paragraph_title, main_layout[0]);
 
+                    if self.showing_report {
+                        frame.render_widget(
+                            create_report_widget(game.accuracy(), true),
+                            main_layout[1],
+                        );
+                        return;
+                    }
+
+                    if self.showing_chart {
+                        Self::render_chart(
+                            frame,
+                            main_layout[1],
+                            &self.chart,
+                            self.chart_name,
+                            game.spot(),
+                            self.draw_mode,
+                        );
+                        return;
+                    }
+
                     let table = self.table.split(main_layout[1]);
 
                     frame.render_widget(my_hand_widget, table[1]);
                     frame.render_widget(dealer_hand_widget, table[2]);
 
-                    let profit_widget = create_profit_widget(game.profit(), game.bet(), table[0]);
-                    frame.render_widget(profit_widget, table[0]);
+                    let money_column = Layout::default()
+                        .direction(Direction::Vertical)
+                        .constraints(vec![Constraint::Min(0), Constraint::Length(CHIPS_HEIGHT)])
+                        .split(table[0]);
+
+                    let profit_widget = create_profit_widget(
+                        game.profit(),
+                        game.balance(),
+                        game.bet(),
+                        advised_bet(game),
+                        money_column[0],
+                    );
+                    frame.render_widget(profit_widget, money_column[0]);
+                    frame.render_widget(create_chips_widget(game), money_column[1]);
 
-                    let deck_widget = create_deck_widget(game.deck_length(), table[3]);
-                    frame.render_widget(deck_widget, table[3]);
+                    let mut deck_area = table[3];
+
+                    if self.showing_count {
+                        let column = Layout::default()
+                            .direction(Direction::Vertical)
+                            .constraints(vec![Constraint::Length(4), Constraint::Min(0)])
+                            .split(deck_area);
+
+                        frame.render_widget(create_count_widget(game), column[0]);
+                        deck_area = column[1];
+                    }
+
+                    if self.showing_odds {
+                        let column = Layout::default()
+                            .direction(Direction::Vertical)
+                            .constraints(vec![Constraint::Length(ODDS_HEIGHT), Constraint::Min(0)])
+                            .split(deck_area);
+
+                        frame.render_widget(create_odds_widget(game), column[0]);
+                        deck_area = column[1];
+                    }
+
+                    let deck_widget = create_deck_widget(game.shoe(), deck_area);
+                    frame.render_widget(deck_widget, deck_area);
                 }
             };
+
+            if let (Some(entry), false) = (bet_entry, matches!(self.draw_mode, DrawMode::Warn)) {
+                let (prompt_widget, prompt_area) = create_bet_entry_widget(entry, area);
+                frame.render_widget(Clear, prompt_area);
+                frame.render_widget(prompt_widget, prompt_area);
+            }
         })?;
 
         Ok(())
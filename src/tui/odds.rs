@@ -0,0 +1,55 @@
+use ratatui::{
+    layout::Alignment,
+    text::Text,
+    widgets::{Block, Paragraph},
+};
+
+use super::Game;
+use blackjack::odds::Odds;
+use blackjack::strategy::{DEALER_BUST, DEALER_CHARLIE};
+
+/// Rows the panel takes up, borders included.
+pub const ODDS_HEIGHT: u16 = 11;
+
+pub fn create_odds_widget(game: &Game) -> Paragraph<'_> {
+    let mut text = Text::default();
+
+    match game.odds() {
+        Some(odds) => {
+            let dealer = odds.dealer.map(|chance| chance * 100.0);
+
+            text.push_line(format!("17 {:>4.1}%  18 {:>4.1}%", dealer[0], dealer[1]));
+            text.push_line(format!("19 {:>4.1}%  20 {:>4.1}%", dealer[2], dealer[3]));
+            text.push_line(format!(
+                "21 {:>4.1}% Bust {:>4.1}%",
+                dealer[4], dealer[DEALER_BUST]
+            ));
+
+            if game.rules().charlie.is_some() {
+                text.push_line(format!("Charlie {:>4.1}%", dealer[DEALER_CHARLIE]));
+            }
+
+            text.push_line("");
+            text.push_line("       Win Push Loss");
+            text.push_line(odds_line("Stand", odds.stand));
+            text.push_line(odds_line("Hit", odds.hit));
+        }
+        None => text.push_line("No hand in play"),
+    }
+
+    Paragraph::new(text).alignment(Alignment::Center).block(
+        Block::bordered()
+            .title("Dealer Odds")
+            .title_alignment(Alignment::Center),
+    )
+}
+
+fn odds_line(name: &str, odds: Odds) -> String {
+    format!(
+        "{:<5} {:>3.0}% {:>3.0}% {:>3.0}%",
+        name,
+        odds.win * 100.0,
+        odds.push * 100.0,
+        odds.loss * 100.0
+    )
+}
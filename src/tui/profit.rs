@@ -3,13 +3,26 @@ use std::cmp::Ordering;
 use ratatui::{
     layout::{Alignment, Rect},
     style::Stylize,
-    widgets::{Block, Paragraph},
+    widgets::{block::Title, Block, Paragraph},
 };
 
-pub fn create_profit_widget<'a>(profit: f32, bet: f32, layout_area: Rect) -> Paragraph<'a> {
+use blackjack::money::Money;
+
+pub fn create_profit_widget<'a>(
+    profit: Money,
+    balance: Option<Money>,
+    bet: Money,
+    advised_bet: Option<Money>,
+    layout_area: Rect,
+) -> Paragraph<'a> {
     let mut paragraph_profit = String::new();
 
-    let mut centre_line = format!("Profit: {}", profit);
+    let bet = match advised_bet {
+        Some(advised_bet) => format!("{} (ramp {})", bet, advised_bet),
+        None => bet.to_string(),
+    };
+
+    let mut centre_line = format!("Profit: {:+}", profit);
 
     let table_height = (layout_area.height - 2) as i32;
     let half_table_height = table_height / 2;
@@ -23,7 +36,7 @@ pub fn create_profit_widget<'a>(profit: f32, bet: f32, layout_area: Rect) -> Par
         centre_line += &format!(", Bet: {}", bet);
     }
 
-    let lines = (profit.abs() / 100.0) as i32;
+    let lines = (profit.abs().as_f64() / 100.0) as i32;
 
     let bar = "█".repeat((layout_area.width - 4) as usize);
 
@@ -43,7 +56,7 @@ pub fn create_profit_widget<'a>(profit: f32, bet: f32, layout_area: Rect) -> Par
                 continue;
             }
 
-            if profit > 0.0 {
+            if profit > Money::ZERO {
                 if height > half_table_height - 2 - lines && height < half_table_height - 1 {
                     paragraph_profit += &bar;
                 }
@@ -59,7 +72,7 @@ pub fn create_profit_widget<'a>(profit: f32, bet: f32, layout_area: Rect) -> Par
                 continue;
             }
 
-            if profit > 0.0 {
+            if profit > Money::ZERO {
                 if height > half_table_height - 1 - lines && height < half_table_height {
                     paragraph_profit += &bar;
                 }
@@ -73,15 +86,23 @@ pub fn create_profit_widget<'a>(profit: f32, bet: f32, layout_area: Rect) -> Par
         paragraph_profit += "\n";
     }
 
+    let mut block = Block::bordered()
+        .title("Money")
+        .title_alignment(Alignment::Center);
+
+    if let Some(balance) = balance {
+        block = block.title(
+            Title::from(format!("Balance: {}", balance))
+                .alignment(Alignment::Center)
+                .position(ratatui::widgets::block::Position::Bottom),
+        );
+    }
+
     let create_paragraph_profit = Paragraph::new(paragraph_profit)
         .alignment(Alignment::Center)
-        .block(
-            Block::bordered()
-                .title("Money")
-                .title_alignment(Alignment::Center),
-        );
+        .block(block);
 
-    match profit.total_cmp(&0.0) {
+    match profit.cmp(&Money::ZERO) {
         Ordering::Equal => create_paragraph_profit,
         Ordering::Greater => create_paragraph_profit.light_green(),
         Ordering::Less => create_paragraph_profit.light_red(),
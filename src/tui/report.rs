@@ -0,0 +1,87 @@
+use ratatui::{
+    layout::Alignment,
+    style::Stylize,
+    text::{Line, Text},
+    widgets::{block::Title, Block, Paragraph},
+};
+
+use blackjack::accuracy::{percentage, Accuracy, KINDS};
+use blackjack::money::Money;
+use blackjack::strategy::value_symbol;
+
+const MISTAKES_SHOWN: usize = 10;
+
+/// The accuracy report, with the cost of mistakes in money as well as bets when they were
+/// played for money.
+pub fn create_report_widget(accuracy: &Accuracy, for_money: bool) -> Paragraph<'_> {
+    let (decisions, correct) = accuracy.total();
+    let (cost, money_cost) = accuracy.cost();
+
+    let mut text = Text::default();
+
+    text.push_line(Line::from("Strategy accuracy").bold());
+    text.push_line(format!(
+        "{} of {} decisions right ({:.1}%)",
+        correct,
+        decisions,
+        percentage(correct, decisions)
+    ));
+
+    let by_kind: Vec<String> = KINDS
+        .iter()
+        .map(|kind| {
+            let (decisions, correct) = accuracy.by_kind(*kind);
+            format!(
+                "{}: {}/{} ({:.0}%)",
+                kind.name(),
+                correct,
+                decisions,
+                percentage(correct, decisions)
+            )
+        })
+        .collect();
+    text.push_line(by_kind.join("   "));
+
+    let mut cost_line = format!("Expected value given up: {:.2} bets", cost);
+
+    if for_money {
+        cost_line += &format!(" ({})", Money::from_f64(money_cost));
+    }
+
+    text.push_line(cost_line);
+    text.push_line("");
+
+    let mistakes = accuracy.mistakes();
+
+    if mistakes.is_empty() {
+        text.push_line(Line::from("No mistakes").light_green());
+    } else {
+        text.push_line(Line::from("Most common mistakes").bold());
+
+        for mistake in mistakes.iter().take(MISTAKES_SHOWN) {
+            text.push_line(
+                Line::from(format!(
+                    "{}x {} v {}: {} instead of {}, -{:.3} bets each",
+                    mistake.count,
+                    mistake.situation,
+                    value_symbol(mistake.up),
+                    mistake.played,
+                    mistake.best,
+                    mistake.cost / mistake.count as f64
+                ))
+                .light_red(),
+            );
+        }
+    }
+
+    Paragraph::new(text).alignment(Alignment::Center).block(
+        Block::bordered()
+            .title("Session Report")
+            .title_alignment(Alignment::Center)
+            .title(
+                Title::from("(Q)uit, any other key to go back")
+                    .alignment(Alignment::Center)
+                    .position(ratatui::widgets::block::Position::Bottom),
+            ),
+    )
+}
@@ -0,0 +1,107 @@
+use ratatui::{
+    layout::Alignment,
+    style::Stylize,
+    text::{Line, Text},
+    widgets::{block::Title, Block, Paragraph},
+};
+
+use blackjack::money::Money;
+use blackjack::simulate::{Results, Simulation};
+
+/// Risk of ruin, N0 and hourly win for each contender, or how far along the hands are while
+/// they are still being played.
+pub fn create_risk_widget<'a>(
+    simulation: &'a Simulation,
+    results: Option<&'a Results>,
+    batches_done: usize,
+) -> Paragraph<'a> {
+    let bankroll = &simulation.bankroll;
+    let mut text = Text::default();
+
+    text.push_line(
+        Line::from(format!(
+            "Bankroll {} at {:.0} hands an hour, trips of {:.0} hours",
+            bankroll.amount, bankroll.hands_per_hour, bankroll.hours
+        ))
+        .bold(),
+    );
+
+    let Some(results) = results else {
+        text.push_line("");
+        text.push_line(format!(
+            "Simulating {} hands... {}%",
+            simulation.hands,
+            batches_done * 100 / simulation.batches().max(1)
+        ));
+
+        return Paragraph::new(text)
+            .alignment(Alignment::Center)
+            .block(risk_block());
+    };
+
+    for ((contender, stats), paths) in simulation
+        .contenders
+        .iter()
+        .zip(&results.stats)
+        .zip(&results.paths)
+    {
+        let risk = stats.risk(bankroll);
+
+        text.push_line("");
+        text.push_line(Line::from(contender.to_string()).bold());
+        text.push_line(format!(
+            "House edge {:+.3}% ± {:.3}% over {} hands",
+            stats.house_edge() * 100.0,
+            stats.house_edge_margin() * 100.0,
+            stats.hands
+        ));
+
+        let hourly = Line::from(format!(
+            "Hourly win {:+} ± {}",
+            Money::from_f64(risk.hourly_win),
+            Money::from_f64(risk.hourly_deviation)
+        ));
+        text.push_line(if risk.hourly_win < 0.0 {
+            hourly.light_red()
+        } else {
+            hourly.light_green()
+        });
+
+        text.push_line(match risk.n0 {
+            Some(n0) => format!(
+                "N0 {:.0} hands ({:.1} hours)",
+                n0,
+                n0 / bankroll.hands_per_hour
+            ),
+            None => "No N0, the house has the edge".to_string(),
+        });
+        text.push_line(format!(
+            "Risk of ruin {:.2}% playing forever, {:.2}% in a trip",
+            risk.ruin * 100.0,
+            risk.trip_ruin * 100.0
+        ));
+
+        if paths.paths > 0 {
+            text.push_line(format!(
+                "Simulated trips: {:.2}% of {} went broke",
+                paths.ruin() * 100.0,
+                paths.paths
+            ));
+        }
+    }
+
+    Paragraph::new(text)
+        .alignment(Alignment::Center)
+        .block(risk_block())
+}
+
+fn risk_block<'a>() -> Block<'a> {
+    Block::bordered()
+        .title("Risk of Ruin")
+        .title_alignment(Alignment::Center)
+        .title(
+            Title::from("(Q)uit")
+                .alignment(Alignment::Center)
+                .position(ratatui::widgets::block::Position::Bottom),
+        )
+}
@@ -1,14 +1,44 @@
 use ratatui::{
     layout::Alignment,
+    text::Text,
     widgets::{Block, Paragraph},
 };
 
-pub fn create_stats_widget<'a>(profit: f32, bet: f32, deck_length: usize) -> Paragraph<'a> {
-    let text = format!(
-        "Profit: {}, Bet: {}\nCards remaining: {}",
-        profit, bet, deck_length
+use super::chips::create_chips_line;
+use super::{advised_bet, Game};
+
+pub fn create_stats_widget<'a>(game: &Game, show_count: bool) -> Paragraph<'a> {
+    let bet = match advised_bet(game) {
+        Some(advised_bet) => format!("{} (ramp {})", game.bet(), advised_bet),
+        None => game.bet().to_string(),
+    };
+
+    let balance = match game.balance() {
+        Some(balance) => format!("Balance: {}, ", balance),
+        None => String::new(),
+    };
+
+    let mut text = format!(
+        "{}Profit: {:+}, Bet: {}\nCards remaining: {}, Discarded: {}",
+        balance,
+        game.profit(),
+        bet,
+        game.shoe().cards_remaining(),
+        game.shoe().discard_len()
     );
 
+    if show_count {
+        text += &format!(
+            ", RC: {:+}, TC: {:+.1}",
+            game.running_count(),
+            game.true_count()
+        );
+    }
+
+    // There is no room for the tray itself, so the chips go under the stats.
+    let mut text = Text::from(text);
+    text.push_line(create_chips_line(game));
+
     Paragraph::new(text).alignment(Alignment::Center).block(
         Block::bordered()
             .title("Stats")